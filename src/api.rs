@@ -1,9 +1,13 @@
 use arrayvec::ArrayString;
 use serde::{de, Deserialize};
-use serde_with::{serde_as, DisplayFromStr};
-use shogi::{Color, Move, Piece, Position, Square};
+use shogi::{Color, Move, Piece, PieceType, Position, Square};
 use std::fmt;
 
+use crate::error::RequestError;
+use crate::variant::Variant;
+
+pub const STARTING_POSITION: &str = "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1";
+
 #[derive(Deserialize, PartialEq, Eq, Copy, Clone)]
 pub enum Orientation {
     #[serde(rename = "black")]
@@ -33,31 +37,207 @@ impl Orientation {
         }
     }
 
-    pub fn x(self, square: Square) -> usize {
-        self.fold(8 - usize::from(square.file()), usize::from(square.file()))
+    pub fn x(self, variant: Variant, square: Square) -> usize {
+        let last = variant.size() - 1;
+        self.fold(last - usize::from(square.file()), usize::from(square.file()))
     }
 
-    pub fn y(self, square: Square) -> usize {
-        self.fold(usize::from(square.rank()), 8 - usize::from(square.rank()))
+    pub fn y(self, variant: Variant, square: Square) -> usize {
+        let last = variant.size() - 1;
+        self.fold(usize::from(square.rank()), last - usize::from(square.rank()))
     }
 
-    pub fn hand_y(self, piece: Piece) -> usize {
+    pub fn hand_y(self, variant: Variant, piece: Piece) -> usize {
+        let index = variant.hand_index(piece.piece_type);
         if self.eq_color(piece.color) {
-            9 - piece.piece_type as usize
+            variant.size() - index
         } else {
-            piece.piece_type as usize - 1
+            index - 1
+        }
+    }
+}
+
+#[derive(Deserialize, PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Coords {
+    #[serde(rename = "none")]
+    None,
+    #[serde(rename = "western")]
+    Western,
+    #[serde(rename = "japanese")]
+    Japanese,
+}
+
+impl Default for Coords {
+    fn default() -> Coords {
+        Coords::None
+    }
+}
+
+impl Coords {
+    /// Label of a file, counted from 0 like `Square::file`.
+    pub fn file_label(self, file: u8) -> char {
+        char::from(b'1' + file)
+    }
+
+    /// Label of a rank, counted from 0 like `Square::rank`.
+    pub fn rank_label(self, rank: u8) -> char {
+        match self {
+            Coords::Japanese => ['一', '二', '三', '四', '五', '六', '七', '八', '九'][usize::from(rank)],
+            _ => char::from(b'a' + rank),
+        }
+    }
+}
+
+#[derive(Deserialize, PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Notation {
+    #[serde(rename = "usi")]
+    Usi,
+    #[serde(rename = "western")]
+    Western,
+    #[serde(rename = "japanese")]
+    Japanese,
+}
+
+#[derive(Deserialize, PartialEq, Eq, Copy, Clone)]
+pub enum Format {
+    #[serde(rename = "gif")]
+    Gif,
+    #[serde(rename = "png")]
+    Png,
+}
+
+impl Default for Format {
+    fn default() -> Format {
+        Format::Gif
+    }
+}
+
+impl Format {
+    /// Picks PNG if the client accepts it, but not GIF.
+    pub fn from_accept(accept: &str) -> Option<Format> {
+        let accepts = |mime| {
+            accept
+                .split(',')
+                .any(|m| m.split(';').next().unwrap_or("").trim() == mime)
+        };
+        if accepts("image/gif") {
+            Some(Format::Gif)
+        } else if accepts("image/apng") || accepts("image/png") {
+            Some(Format::Png)
+        } else {
+            None
+        }
+    }
+
+    pub fn content_type(self, animated: bool) -> &'static str {
+        match self {
+            Format::Gif => "image/gif",
+            Format::Png if animated => "image/apng",
+            Format::Png => "image/png",
         }
     }
 }
 
 pub type PlayerName = ArrayString<100>; // length limited to prevent dos
 
+/// Reads a player name from a game record, truncating names that are too
+/// long instead of dropping them.
+pub fn player_name(value: &str) -> Option<PlayerName> {
+    let mut name = PlayerName::new();
+    for c in value.trim().chars() {
+        if name.try_push(c).is_err() {
+            break;
+        }
+    }
+    if name.is_empty() {
+        None
+    } else {
+        Some(name)
+    }
+}
+
 pub type Comment = ArrayString<255>; // strict length limit for gif comments
 
+pub type PlayerTitle = ArrayString<8>;
+
+pub type Flag = ArrayString<16>; // country code like `JP`
+
+/// Details shown next to the player names, given as separate fields like
+/// `blackTitle` or `whiteRating`.
+#[derive(Deserialize, Default, Clone)]
+pub struct Players {
+    #[serde(rename = "blackTitle")]
+    pub black_title: Option<PlayerTitle>,
+    #[serde(rename = "blackRating")]
+    pub black_rating: Option<u16>,
+    #[serde(rename = "blackRatingDiff")]
+    pub black_rating_diff: Option<i16>,
+    #[serde(rename = "blackFlag")]
+    pub black_flag: Option<Flag>,
+    #[serde(rename = "blackOnline")]
+    pub black_online: Option<bool>,
+    #[serde(default, rename = "blackPatron")]
+    pub black_patron: bool,
+    #[serde(rename = "whiteTitle")]
+    pub white_title: Option<PlayerTitle>,
+    #[serde(rename = "whiteRating")]
+    pub white_rating: Option<u16>,
+    #[serde(rename = "whiteRatingDiff")]
+    pub white_rating_diff: Option<i16>,
+    #[serde(rename = "whiteFlag")]
+    pub white_flag: Option<Flag>,
+    #[serde(rename = "whiteOnline")]
+    pub white_online: Option<bool>,
+    #[serde(default, rename = "whitePatron")]
+    pub white_patron: bool,
+}
+
+impl Players {
+    pub fn info(&self, color: Color) -> PlayerInfo {
+        match color {
+            Color::Black => PlayerInfo {
+                title: self.black_title,
+                rating: self.black_rating,
+                rating_diff: self.black_rating_diff,
+                flag: self.black_flag,
+                online: self.black_online,
+                patron: self.black_patron,
+            },
+            Color::White => PlayerInfo {
+                title: self.white_title,
+                rating: self.white_rating,
+                rating_diff: self.white_rating_diff,
+                flag: self.white_flag,
+                online: self.white_online,
+                patron: self.white_patron,
+            },
+        }
+    }
+}
+
+#[derive(Default, Clone, Copy)]
+pub struct PlayerInfo {
+    pub title: Option<PlayerTitle>,
+    pub rating: Option<u16>,
+    pub rating_diff: Option<i16>,
+    pub flag: Option<Flag>,
+    /// Shows a marker when known, which is dimmed when offline.
+    pub online: Option<bool>,
+    pub patron: bool,
+}
+
+impl PlayerInfo {
+    pub fn is_bot(&self) -> bool {
+        self.title.is_some_and(|title| title.as_str() == "BOT")
+    }
+}
+
 #[derive(Copy, Clone)]
 pub enum CheckSquare {
     No,
     Yes,
+    /// Highlight the king of the side to move, if it is in check.
+    Auto,
     Square(Square),
 }
 
@@ -78,7 +258,7 @@ impl<'de> Deserialize<'de> for CheckSquare {
             type Value = CheckSquare;
 
             fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-                fmt.write_str("square name or bool")
+                fmt.write_str("square name, bool or auto")
             }
 
             fn visit_str<E>(self, name: &str) -> Result<CheckSquare, E>
@@ -89,6 +269,8 @@ impl<'de> Deserialize<'de> for CheckSquare {
                     Ok(CheckSquare::Yes)
                 } else if name == "0" || name == "no" || name == "false" {
                     Ok(CheckSquare::No)
+                } else if name == "auto" {
+                    Ok(CheckSquare::Auto)
                 } else {
                     match Square::from_sfen(name) {
                         Some(sq) => Ok(CheckSquare::Square(sq)),
@@ -113,91 +295,638 @@ impl<'de> Deserialize<'de> for CheckSquare {
 }
 
 impl CheckSquare {
-    pub fn to_square(self, king_pos: Option<Square>) -> Option<Square> {
+    pub fn to_square(self, variant: Variant, pos: &Position) -> Option<Square> {
         match self {
             CheckSquare::No => None,
-            CheckSquare::Yes => king_pos,
+            CheckSquare::Yes => pos.find_king(pos.side_to_move()),
+            CheckSquare::Auto if variant.in_check(pos) => pos.find_king(pos.side_to_move()),
+            CheckSquare::Auto => None,
             CheckSquare::Square(sq) => Some(sq),
         }
     }
 }
 
-#[serde_as]
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Winner {
+    #[serde(rename = "black")]
+    Black,
+    #[serde(rename = "white")]
+    White,
+}
+
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Termination {
+    #[serde(rename = "resignation")]
+    Resignation,
+    #[serde(rename = "checkmate")]
+    Checkmate,
+    #[serde(rename = "timeout")]
+    Timeout,
+    #[serde(rename = "sennichite")]
+    Sennichite,
+    #[serde(rename = "impasse")]
+    Impasse,
+}
+
+/// How the game ended. Without a winner, the game is a draw.
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct GameResult {
+    pub winner: Option<Winner>,
+    pub reason: Termination,
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self.reason {
+            Termination::Resignation => "by resignation",
+            Termination::Checkmate => "by checkmate",
+            Termination::Timeout => "on time",
+            Termination::Sennichite => "by sennichite",
+            Termination::Impasse => "by impasse",
+        };
+        match self.winner {
+            Some(Winner::Black) => write!(f, "Sente wins {}", reason),
+            Some(Winner::White) => write!(f, "Gote wins {}", reason),
+            None => match self.reason {
+                Termination::Sennichite | Termination::Impasse => write!(f, "Draw {}", reason),
+                _ => f.write_str("Draw"),
+            },
+        }
+    }
+}
+
+/// How a game record says the game ended, from the point of view of the side
+/// to move after the last move.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Ending {
+    Lost(Termination),
+    Won(Termination),
+    Drawn(Termination),
+}
+
+impl Ending {
+    pub fn result(self, to_move: Color) -> GameResult {
+        let winner = |color| match color {
+            Color::Black => Winner::Black,
+            Color::White => Winner::White,
+        };
+        match self {
+            Ending::Lost(reason) => GameResult {
+                winner: Some(winner(to_move.flip())),
+                reason,
+            },
+            Ending::Won(reason) => GameResult {
+                winner: Some(winner(to_move)),
+                reason,
+            },
+            Ending::Drawn(reason) => GameResult { winner: None, reason },
+        }
+    }
+}
+
+/// Engine evaluation from the point of view of sente, like `{"cp": 35}` or
+/// `{"mate": -3}`.
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Eval {
+    #[serde(rename = "cp")]
+    Centipawns(i32),
+    #[serde(rename = "mate")]
+    Mate(i32),
+}
+
+impl Eval {
+    /// Share of the eval bar taken by sente, from 0 to 1.
+    pub fn black_share(self) -> f32 {
+        match self {
+            // A logistic curve, so that a few hundred centipawns already
+            // show a clear advantage, while large ones level off.
+            Eval::Centipawns(cp) => 1.0 / (1.0 + (-0.0015 * cp as f32).exp()),
+            Eval::Mate(moves) if moves > 0 => 1.0,
+            Eval::Mate(_) => 0.0,
+        }
+    }
+
+    /// Parses evaluations like `35`, `-120` or `#-3` for mate.
+    fn from_name(name: &str) -> Option<Eval> {
+        match name.strip_prefix('#') {
+            Some(moves) => moves.parse().ok().map(Eval::Mate),
+            None => name.parse().ok().map(Eval::Centipawns),
+        }
+    }
+}
+
+/// Evaluations by ply, with gaps where none is known.
+#[derive(Default)]
+pub struct Evals(pub Vec<Option<Eval>>);
+
+impl<'de> Deserialize<'de> for Evals {
+    fn deserialize<D>(deseralizer: D) -> Result<Evals, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct EvalsVisitor;
+
+        impl<'de> de::Visitor<'de> for EvalsVisitor {
+            type Value = Evals;
+
+            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                fmt.write_str("comma separated evaluations")
+            }
+
+            fn visit_str<E>(self, names: &str) -> Result<Evals, E>
+            where
+                E: de::Error,
+            {
+                if names.trim().is_empty() {
+                    return Ok(Evals::default());
+                }
+                names
+                    .split(',')
+                    .map(str::trim)
+                    .map(|name| match name {
+                        "" => Ok(None),
+                        name => Eval::from_name(name)
+                            .map(Some)
+                            .ok_or_else(|| de::Error::custom("invalid evaluation")),
+                    })
+                    .collect::<Result<_, _>>()
+                    .map(Evals)
+            }
+        }
+
+        deseralizer.deserialize_str(EvalsVisitor)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Brush {
+    Green,
+    Red,
+    Blue,
+    Yellow,
+}
+
+impl Brush {
+    fn from_name(name: &str) -> Option<Brush> {
+        Some(match name {
+            "green" => Brush::Green,
+            "red" => Brush::Red,
+            "blue" => Brush::Blue,
+            "yellow" => Brush::Yellow,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ShapeOrig {
+    Square(Square),
+    /// A piece in hand, for drop arrows.
+    Hand(Piece),
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Shape {
+    Circle {
+        square: Square,
+        brush: Brush,
+    },
+    Arrow {
+        orig: ShapeOrig,
+        dest: Square,
+        brush: Brush,
+    },
+}
+
+impl Shape {
+    /// Parses shapes like `7g7f:green`, `5e:red` or `P*5e:blue`. Lowercase
+    /// drop pieces are taken from the hand of gote. The brush defaults to
+    /// green.
+    fn from_name(name: &str) -> Option<Shape> {
+        let (name, brush) = match name.split_once(':') {
+            Some((name, brush)) => (name, Brush::from_name(brush)?),
+            None => (name, Brush::Green),
+        };
+
+        if name.len() == 2 {
+            return Some(Shape::Circle {
+                square: Square::from_sfen(name)?,
+                brush,
+            });
+        }
+
+        let orig = match name.get(..2)? {
+            drop if drop.ends_with('*') => {
+                let c = drop.chars().next()?;
+                let piece_type = match c.to_ascii_uppercase() {
+                    'P' => PieceType::Pawn,
+                    'L' => PieceType::Lance,
+                    'N' => PieceType::Knight,
+                    'S' => PieceType::Silver,
+                    'G' => PieceType::Gold,
+                    'B' => PieceType::Bishop,
+                    'R' => PieceType::Rook,
+                    _ => return None,
+                };
+                ShapeOrig::Hand(Piece {
+                    piece_type,
+                    color: if c.is_ascii_uppercase() {
+                        Color::Black
+                    } else {
+                        Color::White
+                    },
+                })
+            }
+            orig => ShapeOrig::Square(Square::from_sfen(orig)?),
+        };
+        let dest = Square::from_sfen(name.get(2..)?)?;
+
+        Some(match orig {
+            ShapeOrig::Square(square) if square == dest => Shape::Circle { square, brush },
+            orig => Shape::Arrow { orig, dest, brush },
+        })
+    }
+}
+
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct Shapes(pub Vec<Shape>);
+
+impl<'de> Deserialize<'de> for Shapes {
+    fn deserialize<D>(deseralizer: D) -> Result<Shapes, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct ShapesVisitor;
+
+        impl<'de> de::Visitor<'de> for ShapesVisitor {
+            type Value = Shapes;
+
+            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                fmt.write_str("comma separated shapes or list of shapes")
+            }
+
+            fn visit_str<E>(self, names: &str) -> Result<Shapes, E>
+            where
+                E: de::Error,
+            {
+                names
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(|name| Shape::from_name(name).ok_or_else(|| de::Error::custom("invalid shape")))
+                    .collect::<Result<_, _>>()
+                    .map(Shapes)
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Shapes, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let mut shapes = Vec::new();
+                while let Some(name) = seq.next_element::<String>()? {
+                    shapes.push(Shape::from_name(&name).ok_or_else(|| de::Error::custom("invalid shape"))?);
+                }
+                Ok(Shapes(shapes))
+            }
+        }
+
+        deseralizer.deserialize_any(ShapesVisitor)
+    }
+}
+
 #[derive(Deserialize)]
 pub struct RequestParams {
     pub black: Option<PlayerName>,
     pub white: Option<PlayerName>,
     pub comment: Option<Comment>,
-    #[serde_as(as = "DisplayFromStr")]
-    #[serde(default)]
-    pub sfen: Position,
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default, rename = "lastMove")]
-    pub last_move: Option<Move>,
+    pub sfen: Option<String>,
+    #[serde(rename = "lastMove")]
+    pub last_move: Option<String>,
     #[serde(default)]
     pub check: CheckSquare,
     #[serde(default)]
+    pub variant: Variant,
+    #[serde(default)]
     pub orientation: Orientation,
+    #[serde(default)]
+    pub coords: Coords,
+    #[serde(default)]
+    pub transparent: bool,
+    #[serde(default)]
+    pub shapes: Shapes,
+    pub theme: Option<String>,
+    #[serde(rename = "pieceSet")]
+    pub piece_set: Option<String>,
+    pub scale: Option<u8>,
+    /// Read from the same query, see `Players`.
+    #[serde(skip)]
+    pub players: Players,
+}
+
+#[derive(Deserialize)]
+pub struct RequestGraph {
+    #[serde(default)]
+    pub evals: Evals,
+    /// Index into `evals` to mark, like the ply of a position image.
+    pub ply: Option<usize>,
+    #[serde(default)]
+    pub transparent: bool,
+    pub theme: Option<String>,
+    pub scale: Option<u8>,
 }
 
 #[derive(Deserialize)]
 pub struct RequestBody {
     pub black: Option<PlayerName>,
     pub white: Option<PlayerName>,
+    #[serde(flatten)]
+    pub players: Players,
     pub comment: Option<Comment>,
+    /// May be left out when frames are streamed.
+    #[serde(default)]
     pub frames: Vec<RequestFrame>,
+    /// Shown over the last frame.
+    pub result: Option<GameResult>,
+    #[serde(default)]
+    pub variant: Variant,
     #[serde(default)]
     pub orientation: Orientation,
     #[serde(default)]
+    pub coords: Coords,
+    pub notation: Option<Notation>,
+    /// Reject last moves that do not match the positions, instead of only
+    /// those ending on an empty square.
+    #[serde(default)]
+    pub strict: bool,
+    #[serde(default)]
     pub delay: u16,
+    pub format: Option<Format>,
+    pub theme: Option<String>,
+    #[serde(rename = "pieceSet")]
+    pub piece_set: Option<String>,
+    pub scale: Option<u8>,
 }
 
-#[serde_as]
 #[derive(Deserialize, Default)]
 pub struct RequestFrame {
-    #[serde_as(as = "DisplayFromStr")]
-    #[serde(default)]
-    pub sfen: Position,
+    pub sfen: Option<String>,
     #[serde(default)]
     pub delay: Option<u16>,
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    #[serde(default, rename = "lastMove")]
-    pub last_move: Option<Move>,
+    #[serde(rename = "lastMove")]
+    pub last_move: Option<String>,
     #[serde(default)]
     pub check: CheckSquare,
+    #[serde(default)]
+    pub shapes: Shapes,
+    #[serde(rename = "blackClock")]
+    pub black_clock: Option<u32>,
+    #[serde(rename = "whiteClock")]
+    pub white_clock: Option<u32>,
+    #[serde(rename = "blackByoyomi")]
+    pub black_byoyomi: Option<u32>,
+    #[serde(rename = "whiteByoyomi")]
+    pub white_byoyomi: Option<u32>,
+    pub eval: Option<Eval>,
+}
+
+#[derive(Deserialize)]
+pub struct RequestMoves {
+    pub black: Option<PlayerName>,
+    pub white: Option<PlayerName>,
+    #[serde(flatten)]
+    pub players: Players,
+    pub comment: Option<Comment>,
+    pub sfen: Option<String>,
+    #[serde(default)]
+    pub moves: String,
+    pub result: Option<GameResult>,
+    #[serde(default)]
+    pub variant: Variant,
+    #[serde(default)]
+    pub orientation: Orientation,
+    #[serde(default)]
+    pub coords: Coords,
+    pub notation: Option<Notation>,
+    #[serde(default)]
+    pub delay: u16,
+    pub theme: Option<String>,
+    #[serde(rename = "pieceSet")]
+    pub piece_set: Option<String>,
+    pub scale: Option<u8>,
+}
+
+impl RequestMoves {
+    pub fn into_body(self) -> Result<RequestBody, RequestError> {
+        let sfen = self.sfen.as_deref().unwrap_or(self.variant.starting_position());
+        let pos = self.variant.parse_sfen(sfen)?;
+
+        let moves = self
+            .moves
+            .split_whitespace()
+            .enumerate()
+            .map(|(i, usi)| {
+                self.variant.parse_move(usi).ok_or_else(|| IllegalMove {
+                    ply: i + 1,
+                    usi: usi.to_owned(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(RequestBody {
+            black: self.black,
+            white: self.white,
+            players: self.players,
+            comment: self.comment,
+            frames: RequestFrame::replay(self.variant, pos, moves)?,
+            result: self.result,
+            variant: self.variant,
+            orientation: self.orientation,
+            coords: self.coords,
+            notation: self.notation,
+            strict: false,
+            delay: self.delay,
+            format: None,
+            theme: self.theme,
+            piece_set: self.piece_set,
+            scale: self.scale,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+pub struct RequestPosition {
+    pub black: Option<PlayerName>,
+    pub white: Option<PlayerName>,
+    /// Read from the same query, see `Players`.
+    #[serde(skip)]
+    pub players: Players,
+    pub comment: Option<Comment>,
+    /// Start position and moves in the syntax of the USI `position` command,
+    /// like `startpos moves 7g7f 3c3d` or `sfen <sfen> moves 7g7f`.
+    pub position: String,
+    #[serde(default)]
+    pub variant: Variant,
+    #[serde(default)]
+    pub orientation: Orientation,
+    #[serde(default)]
+    pub coords: Coords,
+    pub notation: Option<Notation>,
+    #[serde(default)]
+    pub delay: u16,
+    pub theme: Option<String>,
+    #[serde(rename = "pieceSet")]
+    pub piece_set: Option<String>,
+    pub scale: Option<u8>,
+}
+
+impl From<RequestPosition> for RequestMoves {
+    fn from(req: RequestPosition) -> RequestMoves {
+        let position = req.position.trim();
+        let position = position.strip_prefix("position").unwrap_or(position).trim_start();
+        let (sfen, moves) = match position.split_once("moves") {
+            Some((sfen, moves)) => (sfen.trim(), moves.trim()),
+            None => (position, ""),
+        };
+        let sfen = sfen.strip_prefix("sfen").unwrap_or(sfen).trim();
+
+        RequestMoves {
+            black: req.black,
+            white: req.white,
+            players: req.players,
+            comment: req.comment,
+            sfen: match sfen {
+                "" | "startpos" => None,
+                sfen => Some(sfen.to_owned()),
+            },
+            moves: moves.to_owned(),
+            result: None,
+            variant: req.variant,
+            orientation: req.orientation,
+            coords: req.coords,
+            notation: req.notation,
+            delay: req.delay,
+            theme: req.theme,
+            piece_set: req.piece_set,
+            scale: req.scale,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct IllegalMove {
+    pub ply: usize,
+    pub usi: String,
+}
+
+impl fmt::Display for IllegalMove {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "illegal move {} at ply {}", self.usi, self.ply)
+    }
+}
+
+impl RequestFrame {
+    fn from_position(variant: Variant, pos: &Position, last_move: Option<Move>) -> RequestFrame {
+        RequestFrame {
+            sfen: Some(variant.to_sfen(pos)),
+            check: CheckSquare::Auto,
+            last_move: last_move.map(|m| variant.move_to_usi(m)),
+            delay: None,
+            shapes: Shapes::default(),
+            black_clock: None,
+            white_clock: None,
+            black_byoyomi: None,
+            white_byoyomi: None,
+            eval: None,
+        }
+    }
+
+    /// Replays moves from a start position, producing one frame for the
+    /// start position and one for each move.
+    pub fn replay<I>(variant: Variant, mut pos: Position, moves: I) -> Result<Vec<RequestFrame>, IllegalMove>
+    where
+        I: IntoIterator<Item = Move>,
+    {
+        let mut frames = vec![RequestFrame::from_position(variant, &pos, None)];
+
+        for (i, m) in moves.into_iter().enumerate() {
+            if !variant.make_move(&mut pos, m) {
+                return Err(IllegalMove {
+                    ply: i + 1,
+                    usi: variant.move_to_usi(m),
+                });
+            }
+            frames.push(RequestFrame::from_position(variant, &pos, Some(m)));
+        }
+
+        Ok(frames)
+    }
+}
+
+/// Players, frames and result extracted from a game record.
+pub struct Record {
+    pub black: Option<PlayerName>,
+    pub white: Option<PlayerName>,
+    pub frames: Vec<RequestFrame>,
+    pub result: Option<GameResult>,
+}
+
+#[derive(Deserialize)]
+pub struct RecordParams {
+    /// Read from the same query, see `Players`.
+    #[serde(skip)]
+    pub players: Players,
+    pub comment: Option<Comment>,
+    #[serde(default)]
+    pub orientation: Orientation,
+    #[serde(default)]
+    pub coords: Coords,
+    pub notation: Option<Notation>,
+    #[serde(default)]
+    pub delay: u16,
+    pub theme: Option<String>,
+    #[serde(rename = "pieceSet")]
+    pub piece_set: Option<String>,
+    pub scale: Option<u8>,
+}
+
+impl RecordParams {
+    pub fn into_body(self, record: Record) -> RequestBody {
+        RequestBody {
+            black: record.black,
+            white: record.white,
+            players: self.players,
+            comment: self.comment,
+            frames: record.frames,
+            result: record.result,
+            variant: Variant::Standard,
+            orientation: self.orientation,
+            coords: self.coords,
+            notation: self.notation,
+            strict: false,
+            delay: self.delay,
+            format: None,
+            theme: self.theme,
+            piece_set: self.piece_set,
+            scale: self.scale,
+        }
+    }
 }
 
 impl RequestBody {
     pub fn example() -> RequestBody {
         let moves = "7g7f 4c4d 8i7g 3c3d 7g6e 2b3c 6e5c+ 3d3e 5c6c 4d4e 8h3c+ 2a3c B*6f 8b4b 6f3c+ 7c7d N*4d B*1e 6c5b 6a5b 4d5b+ 4a5b 3c1a N*3b 1g1f 1e2d G*1e 4b4a 1e2d 2c2d L*6f 3a4b B*9e 5b6b 6f6c+ 4a1a 5g5f B*5d 2h7h 5d6c 7f7e 9c9d 9e7g 9d9e 7g1a+ 6c5b 7e7d 6b6c R*5e L*5c 5e4e N*3c 4e3e G*4d 3e8e 7a7b 1a1b 1c1d 1b2c 5a4a 2c1d 4b3a 1d1e 4d4e 5i4h 5c5f P*5g P*5a 5g5f 4e5f L*4f P*4b P*5g 5f6g 4f4b+ 3a4b 7h7f L*1a 1e2f 8a9c 8e6e P*6d 6e6g 3b4d 2f4d 5b2e 7f4f 6c7d 4d3c P*3a 3c2d 2e4c G*4d 7d7c 4d4c 4b4c 4f4c+ G*4b 2d4b";
         let mut pos = Position::new();
-        pos.set_sfen("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1")
-            .unwrap();
-
-        let mut frames = Vec::with_capacity(100);
-        frames.push(RequestFrame {
-            sfen: Position::from_sfen(&pos.to_sfen()).unwrap(),
-            ..Default::default()
-        });
-
-        for move_str in moves.split(" ") {
-            if move_str.trim().is_empty() || move_str.ends_with(".") {
-                continue;
-            }
-            let m = Move::from_sfen(move_str).unwrap();
-            pos.make_move(m).unwrap();
+        pos.set_sfen(STARTING_POSITION).unwrap();
 
-            frames.push(RequestFrame {
-                sfen: Position::from_sfen(&pos.to_sfen()).unwrap(),
-                check: if pos.in_check(pos.side_to_move()) {
-                    CheckSquare::Yes
-                } else {
-                    CheckSquare::No
-                },
-                last_move: Some(m),
-                delay: None,
-            })
-        }
+        let mut frames = RequestFrame::replay(
+            Variant::Standard,
+            pos,
+            moves
+                .split(' ')
+                .filter(|m| !m.trim().is_empty() && !m.ends_with('.'))
+                .map(|m| Move::from_sfen(m).unwrap()),
+        )
+        .expect("legal example");
 
         frames.last_mut().unwrap().delay = Some(500);
 
@@ -205,9 +934,19 @@ impl RequestBody {
             comment: Some(Comment::from("Nowhere").unwrap()),
             black: Some(PlayerName::from("Sente").unwrap()),
             white: Some(PlayerName::from("Gote").unwrap()),
+            players: Players::default(),
+            variant: Variant::Standard,
             orientation: Orientation::Black,
+            coords: Coords::None,
+            notation: None,
+            strict: false,
             delay: 75,
             frames: frames,
+            result: None,
+            format: None,
+            theme: None,
+            piece_set: None,
+            scale: None,
         }
     }
 }
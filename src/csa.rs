@@ -0,0 +1,481 @@
+use shogi::{Move, Position, Square};
+use std::fmt;
+
+use crate::api::{player_name, Ending, IllegalMove, Record, RequestFrame, Termination};
+use crate::variant::Variant;
+
+const HIRATE: [&str; 9] = [
+    "-KY-KE-GI-KI-OU-KI-GI-KE-KY",
+    " * -HI *  *  *  *  * -KA * ",
+    "-FU-FU-FU-FU-FU-FU-FU-FU-FU",
+    " *  *  *  *  *  *  *  *  * ",
+    " *  *  *  *  *  *  *  *  * ",
+    " *  *  *  *  *  *  *  *  * ",
+    "+FU+FU+FU+FU+FU+FU+FU+FU+FU",
+    " * +KA *  *  *  *  * +HI * ",
+    "+KY+KE+GI+KI+OU+KI+GI+KE+KY",
+];
+
+// Thinking time is sped up tenfold and capped, so that long thinks do not
+// stall the animation.
+const DELAY_PER_SECOND: u32 = 10;
+const MAX_DELAY: u32 = 500;
+
+#[derive(Debug)]
+pub enum CsaError {
+    Encoding,
+    InvalidPosition { line: usize },
+    InvalidStartPosition,
+    InvalidMove { line: usize },
+    IllegalMove(IllegalMove),
+}
+
+impl fmt::Display for CsaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CsaError::Encoding => f.write_str("csa record must be utf-8"),
+            CsaError::InvalidPosition { line } => write!(f, "invalid position on line {}", line),
+            CsaError::InvalidStartPosition => f.write_str("invalid start position"),
+            CsaError::InvalidMove { line } => write!(f, "invalid move on line {}", line),
+            CsaError::IllegalMove(err) => err.fmt(f),
+        }
+    }
+}
+
+/// Piece on the board as a `(sfen letter, promoted)` pair, with uppercase
+/// letters for sente.
+type BoardPiece = (char, bool);
+
+fn piece(code: &str) -> Option<(char, bool)> {
+    Some(match code {
+        "FU" => ('p', false),
+        "KY" => ('l', false),
+        "KE" => ('n', false),
+        "GI" => ('s', false),
+        "KI" => ('g', false),
+        "KA" => ('b', false),
+        "HI" => ('r', false),
+        "OU" => ('k', false),
+        "TO" => ('p', true),
+        "NY" => ('l', true),
+        "NK" => ('n', true),
+        "NG" => ('s', true),
+        "UM" => ('b', true),
+        "RY" => ('r', true),
+        _ => return None,
+    })
+}
+
+fn colored_piece(code: &str) -> Option<BoardPiece> {
+    let (letter, promoted) = piece(code.get(1..)?)?;
+    match code.get(..1)? {
+        "+" => Some((letter.to_ascii_uppercase(), promoted)),
+        "-" => Some((letter, promoted)),
+        _ => None,
+    }
+}
+
+/// Parses a square like `77` into `(file, rank)`, with `None` for `00`.
+fn coords(s: &str) -> Option<Option<(usize, usize)>> {
+    let mut digits = s.chars().map(|c| c.to_digit(10));
+    let file = digits.next()?? as usize;
+    let rank = digits.next()?? as usize;
+    match (file, rank) {
+        (0, 0) => Some(None),
+        (1..=9, 1..=9) => Some(Some((file, rank))),
+        _ => None,
+    }
+}
+
+fn square((file, rank): (usize, usize)) -> Option<Square> {
+    Square::from_sfen(&format!("{}{}", file, (b'a' + rank as u8 - 1) as char))
+}
+
+struct Setup {
+    board: [[Option<BoardPiece>; 9]; 9],
+    hands: Vec<char>,
+    black_to_move: bool,
+}
+
+impl Setup {
+    fn empty() -> Setup {
+        Setup {
+            board: [[None; 9]; 9],
+            hands: Vec::new(),
+            black_to_move: true,
+        }
+    }
+
+    fn at(&mut self, (file, rank): (usize, usize)) -> &mut Option<BoardPiece> {
+        &mut self.board[rank - 1][9 - file]
+    }
+
+    fn set_row(&mut self, rank: usize, row: &str) -> Option<()> {
+        for file in 0..9 {
+            let cell = row.get(file * 3..file * 3 + 3).unwrap_or(" * ");
+            self.board[rank][file] = if cell.trim() == "*" {
+                None
+            } else {
+                Some(colored_piece(cell)?)
+            };
+        }
+        Some(())
+    }
+
+    fn to_sfen(&self) -> String {
+        let mut sfen = String::new();
+        for (rank, row) in self.board.iter().enumerate() {
+            if rank > 0 {
+                sfen.push('/');
+            }
+            let mut empty = 0;
+            for cell in row {
+                match cell {
+                    Some((letter, promoted)) => {
+                        if empty > 0 {
+                            sfen.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        if *promoted {
+                            sfen.push('+');
+                        }
+                        sfen.push(*letter);
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                sfen.push_str(&empty.to_string());
+            }
+        }
+
+        sfen.push_str(if self.black_to_move { " b " } else { " w " });
+
+        let mut hands = String::new();
+        for letter in "RBGSNLPrbgsnlp".chars() {
+            let count = self.hands.iter().filter(|h| **h == letter).count();
+            if count > 1 {
+                hands.push_str(&count.to_string());
+            }
+            if count > 0 {
+                hands.push(letter);
+            }
+        }
+        sfen.push_str(if hands.is_empty() { "-" } else { &hands });
+        sfen.push_str(" 1");
+        sfen
+    }
+}
+
+/// Reads how the game ended from a special move like `%TORYO`, played by the
+/// side to move. Fouls and interruptions have no result.
+fn parse_ending(stmt: &str) -> Option<Ending> {
+    Some(match stmt {
+        "%TORYO" => Ending::Lost(Termination::Resignation),
+        "%TSUMI" => Ending::Lost(Termination::Checkmate),
+        "%TIME_UP" => Ending::Lost(Termination::Timeout),
+        "%KACHI" => Ending::Won(Termination::Impasse),
+        "%SENNICHITE" => Ending::Drawn(Termination::Sennichite),
+        "%JISHOGI" => Ending::Drawn(Termination::Impasse),
+        _ => return None,
+    })
+}
+
+/// Parses the first game of a CSA record.
+pub fn parse(record: &str) -> Result<Record, CsaError> {
+    let mut black = None;
+    let mut white = None;
+    let mut setup = Setup::empty();
+    let mut moves = Vec::new();
+    let mut delays = Vec::new();
+    let mut start = None;
+    let mut ending = None;
+
+    'lines: for (i, line) in record.lines().enumerate() {
+        let line_no = i + 1;
+
+        // Several statements may share one line, separated by commas.
+        for stmt in line.split(',') {
+            let stmt = stmt.trim_end_matches('\r');
+            let invalid_position = || CsaError::InvalidPosition { line: line_no };
+
+            if stmt.starts_with('\'') || stmt.starts_with('$') || stmt.starts_with('V') || stmt.is_empty() {
+                continue;
+            } else if stmt == "/" {
+                break 'lines;
+            } else if let Some(name) = stmt.strip_prefix("N+") {
+                black = player_name(name);
+            } else if let Some(name) = stmt.strip_prefix("N-") {
+                white = player_name(name);
+            } else if let Some(removed) = stmt.strip_prefix("PI") {
+                for (rank, row) in HIRATE.iter().enumerate() {
+                    setup.set_row(rank, row).expect("valid hirate");
+                }
+                for item in removed.as_bytes().chunks(4) {
+                    let item = std::str::from_utf8(item).map_err(|_| invalid_position())?;
+                    let sq = coords(item).flatten().ok_or_else(invalid_position)?;
+                    *setup.at(sq) = None;
+                }
+            } else if let Some(rest) = stmt.strip_prefix('P') {
+                match rest.get(..1) {
+                    Some(sign @ ("+" | "-")) => {
+                        for item in rest.as_bytes()[1..].chunks(4) {
+                            let item = std::str::from_utf8(item).map_err(|_| invalid_position())?;
+                            let code = item.get(2..).ok_or_else(invalid_position)?;
+                            let (letter, promoted) = piece(code).ok_or_else(invalid_position)?;
+                            let letter = if sign == "+" {
+                                letter.to_ascii_uppercase()
+                            } else {
+                                letter
+                            };
+                            match coords(item).ok_or_else(invalid_position)? {
+                                Some(sq) => *setup.at(sq) = Some((letter, promoted)),
+                                None if !promoted && !letter.eq_ignore_ascii_case(&'k') => setup.hands.push(letter),
+                                None => return Err(invalid_position()),
+                            }
+                        }
+                    }
+                    Some(rank) => {
+                        let rank = rank.parse::<usize>().map_err(|_| invalid_position())?;
+                        if !(1..=9).contains(&rank) {
+                            return Err(invalid_position());
+                        }
+                        setup.set_row(rank - 1, &rest[1..]).ok_or_else(invalid_position)?;
+                    }
+                    None => return Err(invalid_position()),
+                }
+            } else if stmt == "+" || stmt == "-" {
+                if moves.is_empty() {
+                    setup.black_to_move = stmt == "+";
+                }
+            } else if stmt.starts_with('+') || stmt.starts_with('-') {
+                let invalid_move = || CsaError::InvalidMove { line: line_no };
+                start.get_or_insert_with(|| setup.to_sfen());
+                let from = coords(stmt.get(1..3).ok_or_else(invalid_move)?).ok_or_else(invalid_move)?;
+                let to = coords(stmt.get(3..5).ok_or_else(invalid_move)?)
+                    .flatten()
+                    .ok_or_else(invalid_move)?;
+                let (letter, promoted) = piece(stmt.get(5..7).ok_or_else(invalid_move)?).ok_or_else(invalid_move)?;
+
+                let usi = match from {
+                    Some(from) => {
+                        let was_promoted = setup.at(from).is_some_and(|(_, p)| p);
+                        let promote = promoted && !was_promoted;
+                        let moved = setup.at(from).take();
+                        *setup.at(to) = moved.map(|(l, _)| (l, promoted));
+                        format!(
+                            "{}{}{}",
+                            square(from).ok_or_else(invalid_move)?,
+                            square(to).ok_or_else(invalid_move)?,
+                            if promote { "+" } else { "" }
+                        )
+                    }
+                    None => {
+                        *setup.at(to) = Some((
+                            if stmt.starts_with('+') {
+                                letter.to_ascii_uppercase()
+                            } else {
+                                letter
+                            },
+                            false,
+                        ));
+                        format!(
+                            "{}*{}",
+                            letter.to_ascii_uppercase(),
+                            square(to).ok_or_else(invalid_move)?
+                        )
+                    }
+                };
+
+                moves.push(Move::from_sfen(&usi).ok_or_else(invalid_move)?);
+                delays.push(None);
+            } else if let Some(seconds) = stmt.strip_prefix('T') {
+                if let (Some(delay), Ok(seconds)) = (delays.last_mut(), seconds.trim().parse::<u32>()) {
+                    *delay = Some(std::cmp::min(seconds.saturating_mul(DELAY_PER_SECOND), MAX_DELAY) as u16);
+                }
+            } else if stmt.starts_with('%') {
+                ending = parse_ending(stmt.trim_end());
+                break 'lines;
+            }
+        }
+    }
+
+    let sfen = start.unwrap_or_else(|| setup.to_sfen());
+    let mut pos = Position::new();
+    pos.set_sfen(&sfen).map_err(|_| CsaError::InvalidStartPosition)?;
+
+    let to_move = if moves.len() % 2 == 0 {
+        pos.side_to_move()
+    } else {
+        pos.side_to_move().flip()
+    };
+    let mut frames = RequestFrame::replay(Variant::Standard, pos, moves).map_err(CsaError::IllegalMove)?;
+
+    // The time spent on a move is how long the previous position stayed on
+    // the board.
+    for (frame, delay) in frames.iter_mut().zip(delays) {
+        frame.delay = delay;
+    }
+
+    Ok(Record {
+        black,
+        white,
+        frames,
+        result: ending.map(|ending| ending.result(to_move)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{GameResult, Winner};
+    use shogi::bitboard::Factory;
+    use std::sync::Once;
+
+    /// Moves can only be replayed once the attack tables are built.
+    fn setup() {
+        static INIT: Once = Once::new();
+        INIT.call_once(Factory::init);
+    }
+
+    fn last_moves(record: &Record) -> Vec<Option<&str>> {
+        record.frames.iter().map(|frame| frame.last_move.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_moves_and_delays() {
+        setup();
+        let record = parse(
+            "V2.2\n\
+             N+Alice\n\
+             N-Bob\n\
+             PI\n\
+             +\n\
+             +7776FU,T12\n\
+             -3334FU\n\
+             T100\n\
+             +8822UM\n\
+             -3122GI\n\
+             T0\n\
+             %TORYO\n\
+             +5958OU\n",
+        )
+        .unwrap();
+        assert_eq!(record.black.as_deref(), Some("Alice"));
+        assert_eq!(record.white.as_deref(), Some("Bob"));
+        assert_eq!(
+            last_moves(&record),
+            [None, Some("7g7f"), Some("3c3d"), Some("8h2b+"), Some("3a2b")]
+        );
+        let delays: Vec<_> = record.frames.iter().map(|frame| frame.delay).collect();
+        assert_eq!(delays, [Some(120), Some(500), None, Some(0), None]);
+        assert_eq!(
+            record.result,
+            Some(GameResult {
+                winner: Some(Winner::White),
+                reason: Termination::Resignation,
+            })
+        );
+    }
+
+    #[test]
+    fn test_handicap() {
+        setup();
+        let record = parse("PI82HI22KA\n-\n-3334FU\n").unwrap();
+        assert_eq!(
+            record.frames[0].sfen.as_deref(),
+            Some("lnsgkgsnl/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1")
+        );
+        assert_eq!(last_moves(&record), [None, Some("3c3d")]);
+        assert_eq!(record.result, None);
+    }
+
+    #[test]
+    fn test_position_and_drop() {
+        setup();
+        let record = parse(
+            "P1 *  *  *  * -OU *  *  *  * \n\
+             P9 *  *  *  * +OU *  *  *  * \n\
+             P+00KI\n\
+             +\n\
+             +0042KI\n",
+        )
+        .unwrap();
+        assert_eq!(record.frames[0].sfen.as_deref(), Some("4k4/9/9/9/9/9/9/9/4K4 b G 1"));
+        assert_eq!(last_moves(&record), [None, Some("G*4b")]);
+    }
+
+    #[test]
+    fn test_results() {
+        setup();
+        let result = |record: &str| parse(record).unwrap().result;
+        assert_eq!(
+            result(
+                "PI
++
++7776FU
+%TIME_UP
+"
+            ),
+            Some(GameResult {
+                winner: Some(Winner::Black),
+                reason: Termination::Timeout,
+            })
+        );
+        assert_eq!(
+            result(
+                "PI
++
+%KACHI
+"
+            ),
+            Some(GameResult {
+                winner: Some(Winner::Black),
+                reason: Termination::Impasse,
+            })
+        );
+        assert_eq!(
+            result(
+                "PI
++
++7776FU
+%SENNICHITE
+"
+            ),
+            Some(GameResult {
+                winner: None,
+                reason: Termination::Sennichite,
+            })
+        );
+        assert_eq!(
+            result(
+                "PI
++
++7776FU
+%CHUDAN
+"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_long_names_are_truncated() {
+        let record = parse(&format!("N+{}\nPI\n+\n", "a".repeat(150))).unwrap();
+        assert_eq!(record.black.map(|name| name.len()), Some(100));
+    }
+
+    #[test]
+    fn test_errors() {
+        setup();
+        assert!(matches!(
+            parse("PI\nP1-XX\n"),
+            Err(CsaError::InvalidPosition { line: 2 })
+        ));
+        assert!(matches!(parse("PI\n+\n+77\n"), Err(CsaError::InvalidMove { line: 3 })));
+        assert!(matches!(
+            parse("PI\n+\n+7776FU\n-3335FU\n"),
+            Err(CsaError::IllegalMove(IllegalMove { ply: 2, usi })) if usi == "3c3e"
+        ));
+    }
+}
@@ -0,0 +1,119 @@
+use std::fmt;
+use warp::http::StatusCode;
+
+use crate::api::IllegalMove;
+use crate::csa::CsaError;
+use crate::kif::KifError;
+use crate::theme::{MIN_SCALE, SCALE};
+use crate::variant::Variant;
+
+pub const MAX_FRAMES: usize = 1000;
+
+pub const MAX_BODY: usize = 1024 * 1024;
+
+#[derive(Debug)]
+pub enum RequestError {
+    InvalidSfen(String),
+    InvalidMove(String),
+    IllegalMove(IllegalMove),
+    InconsistentLastMove(String),
+    MismatchedLastMove(String),
+    NotInVariant(String, Variant),
+    EmptyFrames,
+    TooManyFrames,
+    EmptyEvals,
+    TooManyEvals,
+    InvalidLine(String),
+    InvalidBody(String),
+    BodyTooLarge,
+    InvalidScale(usize),
+    UnknownTheme(String, String),
+    MissingGlyphs(&'static str),
+    Kif(KifError),
+    Csa(CsaError),
+}
+
+impl RequestError {
+    pub fn title(&self) -> &'static str {
+        match self {
+            RequestError::InvalidSfen(_) => "Invalid SFEN",
+            RequestError::InvalidMove(_) => "Invalid USI move",
+            RequestError::IllegalMove(_) => "Illegal move",
+            RequestError::InconsistentLastMove(_) => "Inconsistent last move",
+            RequestError::MismatchedLastMove(_) => "Inconsistent last move",
+            RequestError::NotInVariant(_, _) => "Not in variant",
+            RequestError::EmptyFrames => "Empty frames",
+            RequestError::TooManyFrames => "Too many frames",
+            RequestError::EmptyEvals => "Empty evaluations",
+            RequestError::TooManyEvals => "Too many evaluations",
+            RequestError::InvalidLine(_) => "Invalid line",
+            RequestError::InvalidBody(_) => "Invalid body",
+            RequestError::BodyTooLarge => "Payload too large",
+            RequestError::InvalidScale(_) => "Invalid scale",
+            RequestError::UnknownTheme(_, _) => "Unknown theme",
+            RequestError::MissingGlyphs(_) => "Missing glyphs",
+            RequestError::Kif(_) => "Invalid KIF record",
+            RequestError::Csa(_) => "Invalid CSA record",
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            RequestError::BodyTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RequestError::InvalidSfen(sfen) => write!(f, "invalid sfen {}", sfen),
+            RequestError::InvalidMove(usi) => write!(f, "invalid usi move {}", usi),
+            RequestError::IllegalMove(err) => err.fmt(f),
+            RequestError::InconsistentLastMove(usi) => {
+                write!(f, "last move {} does not end on an occupied square", usi)
+            }
+            RequestError::MismatchedLastMove(usi) => {
+                write!(f, "last move {} does not end on the moved piece", usi)
+            }
+            RequestError::NotInVariant(name, variant) => write!(f, "{} does not exist in {}", name, variant),
+            RequestError::EmptyFrames => f.write_str("at least one frame is required"),
+            RequestError::TooManyFrames => write!(f, "at most {} frames are allowed", MAX_FRAMES),
+            RequestError::EmptyEvals => f.write_str("at least one evaluation is required"),
+            RequestError::TooManyEvals => write!(f, "at most {} evaluations are allowed", MAX_FRAMES),
+            RequestError::InvalidLine(err) => f.write_str(err),
+            RequestError::InvalidBody(err) => f.write_str(err),
+            RequestError::BodyTooLarge => write!(f, "body must be at most {} bytes", MAX_BODY),
+            RequestError::InvalidScale(scale) => {
+                write!(f, "scale must be between {} and {}, got {}", MIN_SCALE, SCALE, scale)
+            }
+            RequestError::UnknownTheme(board, piece_set) => {
+                write!(f, "no theme {} with piece set {}", board, piece_set)
+            }
+            RequestError::MissingGlyphs(what) => write!(f, "the font of the theme cannot write japanese {}", what),
+            RequestError::Kif(err) => err.fmt(f),
+            RequestError::Csa(err) => err.fmt(f),
+        }
+    }
+}
+
+impl warp::reject::Reject for RequestError {}
+
+impl From<IllegalMove> for RequestError {
+    fn from(err: IllegalMove) -> RequestError {
+        RequestError::IllegalMove(err)
+    }
+}
+
+impl From<KifError> for RequestError {
+    fn from(err: KifError) -> RequestError {
+        RequestError::Kif(err)
+    }
+}
+
+impl From<CsaError> for RequestError {
+    fn from(err: CsaError) -> RequestError {
+        RequestError::Csa(err)
+    }
+}
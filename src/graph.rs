@@ -0,0 +1,130 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use gift::{block, Encoder};
+use ndarray::{s, ArrayViewMut2};
+
+use crate::api::{Brush, Coords, Format, RequestGraph};
+use crate::error::{RequestError, MAX_FRAMES};
+use crate::png;
+use crate::theme::Theme;
+use crate::variant::Variant;
+
+/// Renders the advantage of black over the plies as a filled chart, as
+/// wide as a standard position image, so that the two can be stacked.
+pub fn render(theme: &Theme, req: RequestGraph, format: Format) -> Result<Bytes, RequestError> {
+    let evals = req.evals.0;
+    if evals.is_empty() {
+        return Err(RequestError::EmptyEvals);
+    } else if evals.len() > MAX_FRAMES {
+        return Err(RequestError::TooManyEvals);
+    }
+
+    let width = theme.width(Variant::Standard, Coords::None);
+    let height = theme.graph_height();
+    let transparent = if req.transparent {
+        Some(theme.transparent_color())
+    } else {
+        None
+    };
+
+    // Plies without an evaluation keep the previous one.
+    let mut last = 0.5;
+    let shares: Vec<f32> = evals
+        .iter()
+        .map(|eval| {
+            if let Some(eval) = eval {
+                last = eval.black_share();
+            }
+            last
+        })
+        .collect();
+
+    let mut buffer = vec![transparent.unwrap_or_else(|| theme.bar_color()); width * height];
+    {
+        let mut view = ArrayViewMut2::from_shape((height, width), &mut buffer).expect("shape");
+
+        for x in 0..width {
+            let ply = column_ply(shares.len(), width, x);
+            let idx = ply.floor() as usize;
+            let next = shares[(idx + 1).min(shares.len() - 1)];
+            let share = shares[idx] + (next - shares[idx]) * (ply - idx as f32);
+            let filled = ((share * height as f32).round() as usize).min(height);
+            view.slice_mut(s![(height - filled).., x]).fill(theme.text_color());
+        }
+
+        view.slice_mut(s![height / 2, ..]).fill(theme.med_text_color());
+
+        if let Some(ply) = req.ply.filter(|&ply| ply < shares.len()) {
+            let x = ply_column(shares.len(), width, ply);
+            let marker = (width / 200).max(1);
+            let left = x.saturating_sub(marker / 2).min(width - marker);
+            view.slice_mut(s![.., left..(left + marker)])
+                .fill(theme.brush_color(Brush::Yellow));
+        }
+    }
+
+    let mut output = BytesMut::new().writer();
+    match format {
+        Format::Gif => {
+            let mut blocks = Encoder::new(&mut output).into_block_enc();
+
+            blocks.encode(block::Header::default()).expect("enc header");
+
+            blocks
+                .encode(
+                    block::LogicalScreenDesc::default()
+                        .with_screen_height(height as u16)
+                        .with_screen_width(width as u16)
+                        .with_color_table_config(theme.color_table_config()),
+                )
+                .expect("enc logical screen desc");
+
+            blocks
+                .encode(theme.global_color_table().clone())
+                .expect("enc global color table");
+
+            if let Some(idx) = transparent {
+                let mut ctrl = block::GraphicControl::default();
+                ctrl.set_transparent_color_idx(idx);
+                blocks.encode(ctrl).expect("enc graphic control");
+            }
+
+            blocks
+                .encode(
+                    block::ImageDesc::default()
+                        .with_height(height as u16)
+                        .with_width(width as u16),
+                )
+                .expect("enc image desc");
+
+            let mut image_data = block::ImageData::new(buffer.len());
+            image_data.data_mut().extend_from_slice(&buffer);
+            blocks.encode(image_data).expect("enc image data");
+
+            blocks.encode(block::Trailer::default()).expect("enc trailer");
+        }
+        Format::Png => {
+            png::write_header(&mut output, width, height, theme.palette(), transparent).expect("enc png header");
+            png::write_image(&mut output, width, &buffer).expect("enc png image");
+            png::write_end(&mut output).expect("enc png end");
+        }
+    }
+
+    Ok(output.into_inner().freeze())
+}
+
+/// Fractional ply shown in column `x`, spreading the plies over the width.
+fn column_ply(plies: usize, width: usize, x: usize) -> f32 {
+    if plies < 2 || width < 2 {
+        0.0
+    } else {
+        x as f32 * (plies - 1) as f32 / (width - 1) as f32
+    }
+}
+
+fn ply_column(plies: usize, width: usize, ply: usize) -> usize {
+    if plies < 2 {
+        0
+    } else {
+        ply * (width - 1) / (plies - 1)
+    }
+}
@@ -0,0 +1,373 @@
+use shogi::{Move, Position, Square};
+use std::fmt;
+
+use crate::api::{player_name, Ending, IllegalMove, Record, RequestFrame, Termination, STARTING_POSITION};
+use crate::variant::Variant;
+
+#[derive(Debug)]
+pub enum KifError {
+    Encoding,
+    UnknownHandicap(String),
+    UnsupportedPosition,
+    InvalidMove { line: usize },
+    IllegalMove(IllegalMove),
+}
+
+impl fmt::Display for KifError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KifError::Encoding => f.write_str("kif record must be utf-8"),
+            KifError::UnknownHandicap(name) => write!(f, "unknown handicap {}", name),
+            KifError::UnsupportedPosition => f.write_str("board diagrams are not supported"),
+            KifError::InvalidMove { line } => write!(f, "invalid move on line {}", line),
+            KifError::IllegalMove(err) => err.fmt(f),
+        }
+    }
+}
+
+fn handicap_sfen(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "平手" => STARTING_POSITION,
+        "香落ち" => "lnsgkgsn1/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+        "右香落ち" => "1nsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+        "角落ち" => "lnsgkgsnl/1r7/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+        "飛車落ち" => "lnsgkgsnl/7b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+        "飛香落ち" => "lnsgkgsn1/7b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+        "二枚落ち" => "lnsgkgsnl/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+        "三枚落ち" => "lnsgkgsn1/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+        "四枚落ち" => "1nsgkgsn1/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+        "五枚落ち" => "1nsgkgs2/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+        "左五枚落ち" => "2sgkgsn1/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+        "六枚落ち" => "2sgkgs2/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+        "左七枚落ち" => "2sgkg3/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+        "右七枚落ち" => "3gkgs2/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+        "八枚落ち" => "3gkg3/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+        "十枚落ち" => "4k4/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1",
+        _ => return None,
+    })
+}
+
+// Longest names first, so that promoted pieces are not mistaken for a
+// promotion of the following piece.
+const PIECE_NAMES: [(&str, char); 19] = [
+    ("成香", 'L'),
+    ("成桂", 'N'),
+    ("成銀", 'S'),
+    ("歩", 'P'),
+    ("香", 'L'),
+    ("桂", 'N'),
+    ("銀", 'S'),
+    ("金", 'G'),
+    ("角", 'B'),
+    ("飛", 'R'),
+    ("玉", 'K'),
+    ("王", 'K'),
+    ("と", 'P'),
+    ("杏", 'L'),
+    ("圭", 'N'),
+    ("全", 'S'),
+    ("馬", 'B'),
+    ("龍", 'R'),
+    ("竜", 'R'),
+];
+
+fn parse_file(c: char) -> Option<u32> {
+    match c {
+        '１'..='９' => Some(c as u32 - '０' as u32),
+        '1'..='9' => c.to_digit(10),
+        _ => None,
+    }
+}
+
+fn parse_rank(c: char) -> Option<u32> {
+    "一二三四五六七八九"
+        .chars()
+        .position(|r| r == c)
+        .map(|r| r as u32 + 1)
+        .or_else(|| parse_file(c))
+}
+
+fn square(file: u32, rank: u32) -> Option<Square> {
+    let rank = char::from_u32('a' as u32 + rank - 1)?;
+    Square::from_sfen(&format!("{}{}", file, rank))
+}
+
+/// Reads how the game ended from a terminal entry like `投了`, or from a
+/// closing line like `まで77手で詰み` in records without one. Fouls and
+/// interruptions have no result.
+fn parse_ending(text: &str) -> Option<Ending> {
+    let text = match text.strip_prefix("まで") {
+        Some(rest) => rest.split_once("手で").map_or(rest, |(_, rest)| rest),
+        None => text,
+    };
+    Some(if text.starts_with("投了") {
+        Ending::Lost(Termination::Resignation)
+    } else if text.starts_with("詰み") {
+        Ending::Lost(Termination::Checkmate)
+    } else if text.starts_with("切れ負け") || text.starts_with("時間切れ") {
+        Ending::Lost(Termination::Timeout)
+    } else if text.starts_with("入玉勝ち") {
+        Ending::Won(Termination::Impasse)
+    } else if text.starts_with("千日手") {
+        Ending::Drawn(Termination::Sennichite)
+    } else if text.starts_with("持将棋") {
+        Ending::Drawn(Termination::Impasse)
+    } else {
+        return None;
+    })
+}
+
+/// Parses a single move like `７六歩(77)`, `同　角成(88)` or `５五角打`.
+/// Returns `Ok(None)` for terminal entries like `投了`.
+fn parse_move(text: &str, prev_to: Option<Square>) -> Result<Option<Move>, ()> {
+    let mut rest = text;
+
+    let to = if let Some(after) = rest.strip_prefix('同') {
+        rest = after.trim_start_matches(|c: char| c.is_whitespace() || c == '\u{3000}');
+        prev_to.ok_or(())?
+    } else {
+        let mut chars = rest.chars();
+        let file = match chars.next().and_then(parse_file) {
+            Some(file) => file,
+            None => return Ok(None),
+        };
+        let rank = chars.next().and_then(parse_rank).ok_or(())?;
+        rest = chars.as_str();
+        square(file, rank).ok_or(())?
+    };
+
+    let (name, letter) = PIECE_NAMES.iter().find(|(name, _)| rest.starts_with(name)).ok_or(())?;
+    rest = &rest[name.len()..];
+
+    let promote = if let Some(after) = rest.strip_prefix("不成") {
+        rest = after;
+        false
+    } else if let Some(after) = rest.strip_prefix('成') {
+        rest = after;
+        true
+    } else {
+        false
+    };
+
+    let usi = if let Some(origin) = rest.strip_prefix('(') {
+        let mut digits = origin.chars().filter_map(|c| c.to_digit(10));
+        let from = square(digits.next().ok_or(())?, digits.next().ok_or(())?).ok_or(())?;
+        format!("{}{}{}", from, to, if promote { "+" } else { "" })
+    } else {
+        format!("{}*{}", letter, to)
+    };
+
+    Move::from_sfen(&usi).map(Some).ok_or(())
+}
+
+/// Parses the mainline of a KIF record. Variations are ignored.
+pub fn parse(record: &str) -> Result<Record, KifError> {
+    let mut black = None;
+    let mut white = None;
+    let mut sfen = STARTING_POSITION;
+    let mut moves = Vec::new();
+    let mut prev_to = None;
+    let mut ending = None;
+
+    for (i, line) in record.lines().enumerate() {
+        let line = line.trim_start_matches('\u{feff}').trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('*') || line.starts_with('&') {
+            continue;
+        }
+
+        if line.starts_with(|c: char| c.is_ascii_digit()) {
+            let text = line.trim_start_matches(|c: char| c.is_ascii_digit()).trim_start();
+            match parse_move(text, prev_to).map_err(|_| KifError::InvalidMove { line: i + 1 })? {
+                Some(m) => {
+                    prev_to = Some(match m {
+                        Move::Normal { to, .. } => to,
+                        Move::Drop { to, .. } => to,
+                    });
+                    moves.push(m);
+                }
+                None => {
+                    ending = parse_ending(text);
+                    break;
+                }
+            }
+        } else if line.starts_with("変化") {
+            break;
+        } else if line.starts_with("まで") {
+            ending = parse_ending(line);
+        } else if let Some((key, value)) = line.split_once('：').or_else(|| line.split_once(':')) {
+            match key.trim() {
+                "先手" | "下手" => black = player_name(value),
+                "後手" | "上手" => white = player_name(value),
+                "手合割" => {
+                    sfen =
+                        handicap_sfen(value.trim()).ok_or_else(|| KifError::UnknownHandicap(value.trim().to_owned()))?
+                }
+                "先手の持駒" | "後手の持駒" | "下手の持駒" | "上手の持駒" => {
+                    return Err(KifError::UnsupportedPosition)
+                }
+                _ => (),
+            }
+        }
+    }
+
+    let mut pos = Position::new();
+    pos.set_sfen(sfen).expect("valid handicap sfen");
+
+    let to_move = if moves.len() % 2 == 0 {
+        pos.side_to_move()
+    } else {
+        pos.side_to_move().flip()
+    };
+    let frames = RequestFrame::replay(Variant::Standard, pos, moves).map_err(KifError::IllegalMove)?;
+
+    Ok(Record {
+        black,
+        white,
+        frames,
+        result: ending.map(|ending| ending.result(to_move)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{GameResult, Winner};
+    use shogi::bitboard::Factory;
+    use std::sync::Once;
+
+    /// Builds the attack tables that replaying moves relies on, like `main`
+    /// does on startup.
+    fn setup() {
+        static INIT: Once = Once::new();
+        INIT.call_once(Factory::init);
+    }
+
+    fn last_moves(record: &Record) -> Vec<Option<&str>> {
+        record.frames.iter().map(|frame| frame.last_move.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_handicap() {
+        setup();
+        let record = parse(
+            "手合割：角落ち\n\
+             下手：Alice\n\
+             上手：Bob\n\
+             手数----指手---------消費時間--\n\
+             \u{20}  1 ３四歩(33)   ( 0:01/00:00:01)\n\
+             \u{20}  2 ７六歩(77)   ( 0:02/00:00:02)\n",
+        )
+        .unwrap();
+        assert_eq!(record.black.as_deref(), Some("Alice"));
+        assert_eq!(record.white.as_deref(), Some("Bob"));
+        assert_eq!(
+            record.frames[0].sfen.as_deref(),
+            Some("lnsgkgsnl/1r7/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1")
+        );
+        assert_eq!(last_moves(&record), [None, Some("3c3d"), Some("7g7f")]);
+    }
+
+    #[test]
+    fn test_same_square_and_drop() {
+        setup();
+        let record = parse(
+            "1 ７六歩(77)\n\
+             2 ３四歩(33)\n\
+             3 ２二角成(88)\n\
+             4 同　銀(31)\n\
+             5 ４五角打\n\
+             6 投了\n\
+             7 ５八金(49)\n",
+        )
+        .unwrap();
+        assert_eq!(
+            last_moves(&record),
+            [
+                None,
+                Some("7g7f"),
+                Some("3c3d"),
+                Some("8h2b+"),
+                Some("3a2b"),
+                Some("B*4e")
+            ]
+        );
+        assert_eq!(
+            record.result,
+            Some(GameResult {
+                winner: Some(Winner::Black),
+                reason: Termination::Resignation,
+            })
+        );
+    }
+
+    #[test]
+    fn test_no_promotion() {
+        setup();
+        let record = parse("1 ７六歩(77)\n2 ３四歩(33)\n3 ２二角不成(88)\n").unwrap();
+        assert_eq!(record.frames[3].last_move.as_deref(), Some("8h2b"));
+        assert_eq!(
+            record.frames[3].sfen.as_deref(),
+            Some("lnsgkgsnl/1r5B1/pppppp1pp/6p2/9/2P6/PP1PPPPPP/7R1/LNSGKGSNL w B 4")
+        );
+    }
+
+    #[test]
+    fn test_results() {
+        setup();
+        let result = |record: &str| parse(record).unwrap().result;
+        assert_eq!(
+            result("手合割：角落ち\n1 ３四歩(33)\n2 切れ負け\n"),
+            Some(GameResult {
+                winner: Some(Winner::White),
+                reason: Termination::Timeout,
+            })
+        );
+        assert_eq!(
+            result("1 ７六歩(77)\n2 千日手\n"),
+            Some(GameResult {
+                winner: None,
+                reason: Termination::Sennichite,
+            })
+        );
+        assert_eq!(
+            result("1 ７六歩(77)\nまで1手で詰み\n"),
+            Some(GameResult {
+                winner: Some(Winner::Black),
+                reason: Termination::Checkmate,
+            })
+        );
+        assert_eq!(result("1 ７六歩(77)\n2 中断\n"), None);
+    }
+
+    #[test]
+    fn test_variations_are_ignored() {
+        setup();
+        let record = parse("1 ７六歩(77)\n2 ３四歩(33)\n\n変化：2手\n2 ８四歩(83)\n").unwrap();
+        assert_eq!(last_moves(&record), [None, Some("7g7f"), Some("3c3d")]);
+    }
+
+    #[test]
+    fn test_errors() {
+        setup();
+        assert!(matches!(
+            parse("手合割：九枚落ち\n"),
+            Err(KifError::UnknownHandicap(name)) if name == "九枚落ち"
+        ));
+        assert!(matches!(
+            parse("先手の持駒：なし\n"),
+            Err(KifError::UnsupportedPosition)
+        ));
+        assert!(matches!(
+            parse("1 ７六歩(77)\n2 ３四\n"),
+            Err(KifError::InvalidMove { line: 2 })
+        ));
+        assert!(matches!(
+            parse("1 同　歩(77)\n"),
+            Err(KifError::InvalidMove { line: 1 })
+        ));
+        assert!(matches!(
+            parse("1 ７六歩(77)\n2 ７五歩(73)\n"),
+            Err(KifError::IllegalMove(IllegalMove { ply: 2, usi })) if usi == "7c7e"
+        ));
+    }
+}
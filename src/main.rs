@@ -1,6 +1,9 @@
-use std::{convert::Infallible, net::SocketAddr};
+use std::{convert::Infallible, net::SocketAddr, path::PathBuf};
 
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 use clap::Parser;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio_stream::{wrappers::ReceiverStream, Stream, StreamExt};
 use warp::{
     http::{status::StatusCode, Response},
     hyper::Body,
@@ -8,13 +11,25 @@ use warp::{
 };
 
 mod api;
+mod csa;
+mod error;
+mod graph;
+mod kif;
+mod ndjson;
+mod notation;
+mod png;
 mod render;
+mod svg;
 mod theme;
+mod variant;
 
-use api::{RequestBody, RequestParams};
+use api::{Format, Players, RecordParams, RequestBody, RequestGraph, RequestMoves, RequestParams, RequestPosition};
+use csa::CsaError;
+use error::{RequestError, MAX_BODY, MAX_FRAMES};
+use kif::KifError;
 use render::Render;
 use shogi::bitboard::Factory as BBFactory;
-use theme::Theme;
+use theme::ThemeRegistry;
 
 #[derive(Parser)]
 struct Opt {
@@ -24,28 +39,260 @@ struct Opt {
     /// Listen on this port
     #[clap(long = "port", default_value = "6175")]
     port: u16,
+    /// Load sprites and fonts listed in theme.json of this directory,
+    /// instead of the embedded theme
+    #[clap(long = "theme-dir")]
+    theme_dir: Option<PathBuf>,
 }
 
-fn image(theme: &'static Theme, req: RequestParams) -> impl warp::Reply {
+#[derive(Serialize)]
+struct Problem {
+    title: &'static str,
+    status: u16,
+    detail: String,
+}
+
+fn reject(err: impl Into<RequestError>) -> warp::Rejection {
+    warp::reject::custom(err.into())
+}
+
+/// Reads the whole request body. The size limit is checked while the body
+/// streams in, so that chunked bodies without `Content-Length` work too.
+async fn read_body<S, B>(body: S) -> Result<Bytes, warp::Rejection>
+where
+    S: Stream<Item = Result<B, warp::Error>>,
+    B: Buf,
+{
+    let mut body = Box::pin(body);
+    let mut buffer = BytesMut::new();
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.map_err(|err| reject(RequestError::InvalidBody(err.to_string())))?;
+        if buffer.len() + chunk.remaining() > MAX_BODY {
+            return Err(reject(RequestError::BodyTooLarge));
+        }
+        buffer.put(chunk);
+    }
+    Ok(buffer.freeze())
+}
+
+fn body() -> impl Filter<Extract = (Bytes,), Error = warp::Rejection> + Clone {
+    warp::body::stream().and_then(read_body)
+}
+
+fn json_body<T: DeserializeOwned + Send>() -> impl Filter<Extract = (T,), Error = warp::Rejection> + Clone {
+    body().and_then(|body: Bytes| async move {
+        serde_json::from_slice(&body).map_err(|err| reject(RequestError::InvalidBody(err.to_string())))
+    })
+}
+
+fn stream(render: Render, content_type: &str) -> Result<Response<Body>, warp::http::Error> {
     Response::builder()
         .status(StatusCode::OK)
-        .header("Content-Type", "image/gif")
-        .body(Body::wrap_stream(tokio_stream::iter(
-            Render::new_image(theme, req).map(Ok::<_, Infallible>),
-        )))
+        .header("Content-Type", content_type)
+        .body(Body::wrap_stream(tokio_stream::iter(render.map(Ok::<_, Infallible>))))
 }
 
-fn game(theme: &'static Theme, req: RequestBody) -> impl warp::Reply {
-    Response::builder()
+async fn image(
+    themes: &'static ThemeRegistry,
+    req: RequestParams,
+    format: Format,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let theme = themes
+        .current()
+        .get(req.theme.as_deref(), req.piece_set.as_deref(), req.scale)
+        .map_err(reject)?;
+    Ok(stream(
+        Render::new_image(theme, req, format).map_err(reject)?,
+        format.content_type(false),
+    ))
+}
+
+async fn image_svg(themes: &'static ThemeRegistry, req: RequestParams) -> Result<impl warp::Reply, warp::Rejection> {
+    let theme = themes
+        .current()
+        .get(req.theme.as_deref(), req.piece_set.as_deref(), req.scale)
+        .map_err(reject)?;
+    Ok(warp::reply::with_header(
+        svg::render(&theme, req).map_err(reject)?,
+        "Content-Type",
+        "image/svg+xml",
+    ))
+}
+
+async fn graph(
+    themes: &'static ThemeRegistry,
+    req: RequestGraph,
+    format: Format,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let theme = themes
+        .current()
+        .get(req.theme.as_deref(), None, req.scale)
+        .map_err(reject)?;
+    let bytes = graph::render(&theme, req, format).map_err(reject)?;
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", format.content_type(false))
+        .body(Body::from(bytes)))
+}
+
+async fn game(themes: &'static ThemeRegistry, req: RequestBody) -> Result<impl warp::Reply, warp::Rejection> {
+    let theme = themes
+        .current()
+        .get(req.theme.as_deref(), req.piece_set.as_deref(), req.scale)
+        .map_err(reject)?;
+    // A single frame is written as a plain PNG, without animation chunks.
+    let content_type = req.format.unwrap_or_default().content_type(req.frames.len() > 1);
+    Ok(stream(Render::new_animation(theme, req).map_err(reject)?, content_type))
+}
+
+/// Renders frames as they arrive as lines of JSON. The first line is a game
+/// like for `game`, and each following line is one more frame.
+async fn live<S, B>(themes: &'static ThemeRegistry, body: S) -> Result<impl warp::Reply, warp::Rejection>
+where
+    S: Stream<Item = Result<B, warp::Error>> + Send + 'static,
+    B: Buf + Send,
+{
+    let mut lines = ndjson::Lines::new(Box::pin(body), MAX_BODY);
+    let header = lines
+        .next()
+        .await
+        .map_err(reject)?
+        .ok_or_else(|| reject(RequestError::InvalidLine("missing game header".to_owned())))?;
+    let req: RequestBody =
+        serde_json::from_slice(&header).map_err(|err| reject(RequestError::InvalidLine(err.to_string())))?;
+    let theme = themes
+        .current()
+        .get(req.theme.as_deref(), req.piece_set.as_deref(), req.scale)
+        .map_err(reject)?;
+    let players = format!(
+        "{} - {}",
+        req.black.as_ref().map_or("?", |name| name.as_str()),
+        req.white.as_ref().map_or("?", |name| name.as_str())
+    );
+    let (mut render, mut preamble) = Render::new_stream(theme, req).map_err(reject)?;
+
+    // Wait for the first frame, so that a stream without any is rejected
+    // rather than answered with an empty board.
+    if preamble.is_empty() {
+        let line = lines
+            .next()
+            .await
+            .map_err(reject)?
+            .ok_or_else(|| reject(RequestError::EmptyFrames))?;
+        let frame = serde_json::from_slice(&line).map_err(|err| reject(RequestError::InvalidLine(err.to_string())))?;
+        preamble.push(render.push(frame).map_err(reject)?);
+    }
+
+    // Frames are rendered as they come in, until the body or a bad line
+    // ends the input. The image is then completed with the trailer.
+    let (tx, rx) = tokio::sync::mpsc::channel(16);
+    tokio::spawn(async move {
+        let mut frames = preamble.len();
+        for bytes in preamble {
+            if tx.send(bytes).await.is_err() {
+                return;
+            }
+        }
+        while frames < MAX_FRAMES {
+            let frame = match lines.next().await {
+                Ok(Some(line)) => {
+                    serde_json::from_slice(&line).map_err(|err| RequestError::InvalidLine(err.to_string()))
+                }
+                Ok(None) => break,
+                Err(err) => Err(err),
+            };
+            let bytes = match frame.and_then(|frame| render.push(frame)) {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    eprintln!("ending live gif {} after {} frames: {}", players, frames, err);
+                    break;
+                }
+            };
+            if tx.send(bytes).await.is_err() {
+                return;
+            }
+            frames += 1;
+        }
+        for bytes in render {
+            if tx.send(bytes).await.is_err() {
+                return;
+            }
+        }
+    });
+
+    Ok(Response::builder()
         .status(StatusCode::OK)
-        .header("Content-Type", "image/gif")
-        .body(Body::wrap_stream(tokio_stream::iter(
-            Render::new_animation(theme, req).map(Ok::<_, Infallible>),
-        )))
+        .header("Content-Type", Format::Gif.content_type(true))
+        .body(Body::wrap_stream(ReceiverStream::new(rx).map(Ok::<_, Infallible>))))
+}
+
+async fn kif(
+    themes: &'static ThemeRegistry,
+    params: RecordParams,
+    record: Bytes,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let record = std::str::from_utf8(&record)
+        .map_err(|_| KifError::Encoding)
+        .and_then(kif::parse)
+        .map_err(reject)?;
+    game(themes, params.into_body(record)).await
 }
 
-fn example(theme: &'static Theme) -> impl warp::Reply {
-    game(theme, RequestBody::example())
+async fn csa(
+    themes: &'static ThemeRegistry,
+    params: RecordParams,
+    record: Bytes,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let record = std::str::from_utf8(&record)
+        .map_err(|_| CsaError::Encoding)
+        .and_then(csa::parse)
+        .map_err(reject)?;
+    game(themes, params.into_body(record)).await
+}
+
+async fn moves(themes: &'static ThemeRegistry, req: RequestMoves) -> Result<impl warp::Reply, warp::Rejection> {
+    game(themes, req.into_body().map_err(reject)?).await
+}
+
+async fn example(themes: &'static ThemeRegistry) -> Result<impl warp::Reply, warp::Rejection> {
+    game(themes, RequestBody::example()).await
+}
+
+async fn recover(rejection: warp::Rejection) -> Result<impl warp::Reply, warp::Rejection> {
+    let (status, title, detail) = if let Some(err) = rejection.find::<RequestError>() {
+        (err.status(), err.title(), err.to_string())
+    } else if let Some(err) = rejection.find::<warp::reject::InvalidQuery>() {
+        (StatusCode::BAD_REQUEST, "Invalid query", err.to_string())
+    } else {
+        return Err(rejection);
+    };
+
+    Ok(warp::reply::with_header(
+        warp::reply::with_status(
+            warp::reply::json(&Problem {
+                title,
+                status: status.as_u16(),
+                detail,
+            }),
+            status,
+        ),
+        "Content-Type",
+        "application/problem+json",
+    ))
+}
+
+#[cfg(unix)]
+async fn reload_on_hangup(themes: &'static ThemeRegistry) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangup = signal(SignalKind::hangup()).expect("install SIGHUP handler");
+    while hangup.recv().await.is_some() {
+        match tokio::task::spawn_blocking(move || themes.reload()).await {
+            Ok(Ok(())) => eprintln!("themes reloaded"),
+            Ok(Err(err)) => eprintln!("keeping previous themes: {}", err),
+            Err(err) => eprintln!("keeping previous themes: {}", err),
+        }
+    }
 }
 
 #[tokio::main]
@@ -53,28 +300,133 @@ async fn main() {
     let opt = Opt::parse();
     let bind = SocketAddr::new(opt.address.parse().expect("valid address"), opt.port);
 
-    let theme: &'static Theme = Box::leak(Box::new(Theme::new()));
+    let themes = ThemeRegistry::new(opt.theme_dir).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    });
+    let themes: &'static ThemeRegistry = Box::leak(Box::new(themes));
+
+    #[cfg(unix)]
+    tokio::spawn(reload_on_hangup(themes));
 
     BBFactory::init();
 
     let image_route = warp::path!("image.gif")
         .and(warp::get())
-        .map(move || theme)
+        .map(move || themes)
+        .and(warp::query::query())
+        .and(warp::query::query())
+        .and_then(|themes, mut req: RequestParams, players: Players| {
+            req.players = players;
+            image(themes, req, Format::Gif)
+        });
+
+    let image_png_route = warp::path!("image.png")
+        .and(warp::get())
+        .map(move || themes)
+        .and(warp::query::query())
+        .and(warp::query::query())
+        .and_then(|themes, mut req: RequestParams, players: Players| {
+            req.players = players;
+            image(themes, req, Format::Png)
+        });
+
+    let image_svg_route = warp::path!("image.svg")
+        .and(warp::get())
+        .map(move || themes)
+        .and(warp::query::query())
+        .and(warp::query::query())
+        .and_then(|themes, mut req: RequestParams, players: Players| {
+            req.players = players;
+            image_svg(themes, req)
+        });
+
+    let graph_route = warp::path!("graph.gif")
+        .and(warp::get())
+        .map(move || themes)
+        .and(warp::query::query())
+        .and_then(|themes, req: RequestGraph| graph(themes, req, Format::Gif));
+
+    let graph_png_route = warp::path!("graph.png")
+        .and(warp::get())
+        .map(move || themes)
         .and(warp::query::query())
-        .map(image);
+        .and_then(|themes, req: RequestGraph| graph(themes, req, Format::Png));
 
     let game_route = warp::path!("game.gif")
         .and(warp::post())
-        .map(move || theme)
-        .and(warp::body::json())
-        .map(game);
+        .map(move || themes)
+        .and(json_body())
+        .and(warp::header::optional("accept"))
+        .and_then(|themes, mut req: RequestBody, accept: Option<String>| {
+            req.format = req.format.or_else(|| accept.as_deref().and_then(Format::from_accept));
+            game(themes, req)
+        });
+
+    let live_route = warp::path!("live.gif")
+        .and(warp::post())
+        .map(move || themes)
+        .and(warp::body::stream())
+        .and_then(live);
+
+    let kif_route = warp::path!("kif.gif")
+        .and(warp::post())
+        .map(move || themes)
+        .and(warp::query::query())
+        .and(warp::query::query())
+        .and(body())
+        .and_then(|themes, mut params: RecordParams, players: Players, record: Bytes| {
+            params.players = players;
+            kif(themes, params, record)
+        });
+
+    let csa_route = warp::path!("csa.gif")
+        .and(warp::post())
+        .map(move || themes)
+        .and(warp::query::query())
+        .and(warp::query::query())
+        .and(body())
+        .and_then(|themes, mut params: RecordParams, players: Players, record: Bytes| {
+            params.players = players;
+            csa(themes, params, record)
+        });
+
+    let moves_route = warp::path!("moves.gif")
+        .and(warp::post())
+        .map(move || themes)
+        .and(json_body())
+        .and_then(moves);
+
+    let position_route = warp::path!("moves.gif")
+        .and(warp::get())
+        .map(move || themes)
+        .and(warp::query::query())
+        .and(warp::query::query())
+        .and_then(|themes, mut req: RequestPosition, players: Players| {
+            req.players = players;
+            moves(themes, req.into())
+        });
 
     let example_route = warp::path!("example.gif")
         .and(warp::get())
-        .map(move || theme)
-        .map(example);
+        .map(move || themes)
+        .and_then(example);
 
-    warp::serve(example_route.or(image_route).or(game_route))
-        .run(bind)
-        .await;
+    warp::serve(
+        example_route
+            .or(image_route)
+            .or(image_png_route)
+            .or(image_svg_route)
+            .or(graph_route)
+            .or(graph_png_route)
+            .or(game_route)
+            .or(live_route)
+            .or(kif_route)
+            .or(csa_route)
+            .or(moves_route)
+            .or(position_route)
+            .recover(recover),
+    )
+    .run(bind)
+    .await;
 }
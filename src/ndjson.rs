@@ -0,0 +1,59 @@
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use std::fmt::Display;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::error::RequestError;
+
+/// Splits a streamed request body into lines, handing out each line as soon
+/// as it is complete.
+pub struct Lines<S> {
+    body: S,
+    buffer: BytesMut,
+    max_len: usize,
+    done: bool,
+}
+
+impl<S, B, E> Lines<S>
+where
+    S: Stream<Item = Result<B, E>> + Unpin,
+    B: Buf,
+    E: Display,
+{
+    pub fn new(body: S, max_len: usize) -> Lines<S> {
+        Lines {
+            body,
+            buffer: BytesMut::new(),
+            max_len,
+            done: false,
+        }
+    }
+
+    /// The next line that is not blank. The last line does not need to be
+    /// terminated.
+    pub async fn next(&mut self) -> Result<Option<Bytes>, RequestError> {
+        loop {
+            let line = match self.buffer.iter().position(|&b| b == b'\n') {
+                Some(end) => self.buffer.split_to(end + 1).freeze(),
+                None if self.done && !self.buffer.is_empty() => self.buffer.split().freeze(),
+                None if self.done => return Ok(None),
+                None if self.buffer.len() > self.max_len => {
+                    return Err(RequestError::InvalidLine(format!(
+                        "line longer than {} bytes",
+                        self.max_len
+                    )))
+                }
+                None => {
+                    match self.body.next().await {
+                        Some(Ok(chunk)) => self.buffer.put(chunk),
+                        Some(Err(err)) => return Err(RequestError::InvalidLine(err.to_string())),
+                        None => self.done = true,
+                    }
+                    continue;
+                }
+            };
+            if !line.iter().all(u8::is_ascii_whitespace) {
+                return Ok(Some(line));
+            }
+        }
+    }
+}
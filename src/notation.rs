@@ -0,0 +1,198 @@
+use shogi::{Color, Move, Piece, PieceType, Position, Square};
+
+use crate::api::Notation;
+use crate::variant::Variant;
+
+const FILES: [char; 9] = ['１', '２', '３', '４', '５', '６', '７', '８', '９'];
+
+const RANKS: [char; 9] = ['一', '二', '三', '四', '五', '六', '七', '八', '九'];
+
+/// A move with what it did to the position, as needed to write it in any
+/// of the notations.
+struct Written {
+    m: Move,
+    piece: Piece,
+    capture: bool,
+    promotion: bool,
+    declined: bool,
+}
+
+impl Written {
+    /// Reconstructs the move leading from `prev` to `pos`. Without the
+    /// previous position, captures are not known.
+    fn new(variant: Variant, prev: Option<&Position>, pos: &Position, m: Move) -> Option<Written> {
+        let after = (*pos.piece_at(destination(m)))?;
+        Some(match m {
+            Move::Normal { from, to, promote } => {
+                let piece = match prev.and_then(|prev| *prev.piece_at(from)) {
+                    Some(piece) if piece.color == after.color => piece,
+                    _ if variant == Variant::Kyotoshogi => flip(after),
+                    _ if promote => after.unpromote().unwrap_or(after),
+                    _ => after,
+                };
+                // Kyoto shogi pieces flip on every move, which is not a
+                // promotion that could have been declined.
+                let promotion =
+                    variant != Variant::Kyotoshogi && !piece.piece_type.is_promoted() && after.piece_type.is_promoted();
+                Written {
+                    m,
+                    piece,
+                    capture: prev.is_some_and(|prev| prev.piece_at(to).is_some()),
+                    promotion,
+                    declined: !promotion
+                        && piece.piece_type.promote().is_some()
+                        && (variant.in_promotion_zone(piece.color, from) || variant.in_promotion_zone(piece.color, to)),
+                }
+            }
+            Move::Drop { piece_type, .. } => Written {
+                m,
+                piece: Piece {
+                    piece_type,
+                    color: after.color,
+                },
+                capture: false,
+                promotion: false,
+                declined: false,
+            },
+        })
+    }
+}
+
+fn destination(m: Move) -> Square {
+    match m {
+        Move::Normal { to, .. } | Move::Drop { to, .. } => to,
+    }
+}
+
+/// Flips a kyoto shogi piece to its other face.
+fn flip(piece: Piece) -> Piece {
+    if piece.piece_type.is_promoted() {
+        piece.unpromote().unwrap_or(piece)
+    } else {
+        piece.promote().unwrap_or(piece)
+    }
+}
+
+/// Writes the move that leads from `prev` to `pos`, prefixed by its move
+/// number. `prev_move` is the move before, which Japanese notation refers
+/// back to when recapturing on the same square.
+pub fn write_move(
+    notation: Notation,
+    variant: Variant,
+    prev: Option<&Position>,
+    prev_move: Option<Move>,
+    pos: &Position,
+    m: Move,
+) -> String {
+    let text = match Written::new(variant, prev, pos, m) {
+        Some(written) => match notation {
+            Notation::Usi => variant.move_to_usi(m),
+            Notation::Western => western(variant, prev, &written),
+            Notation::Japanese => japanese(variant, prev_move, &written),
+        },
+        None => variant.move_to_usi(m),
+    };
+    match pos.ply().checked_sub(1) {
+        Some(number) if number > 0 => format!("{}. {}", number, text),
+        _ => text,
+    }
+}
+
+/// Hodges notation, like `P-7f`, `Bx2b+`, `S*5e` or `G6i-5h`.
+fn western(variant: Variant, prev: Option<&Position>, written: &Written) -> String {
+    let letter = Piece {
+        color: Color::Black,
+        piece_type: variant.face(written.piece).piece_type,
+    };
+    match written.m {
+        Move::Normal { from, to, .. } => {
+            let origin = match prev {
+                Some(prev) if ambiguous(variant, prev, written.piece, from, to) => from.to_string(),
+                _ => String::new(),
+            };
+            format!(
+                "{}{}{}{}{}",
+                letter,
+                origin,
+                if written.capture { 'x' } else { '-' },
+                to,
+                if written.promotion {
+                    "+"
+                } else if written.declined {
+                    "="
+                } else {
+                    ""
+                }
+            )
+        }
+        Move::Drop { to, .. } => format!("{}*{}", letter, to),
+    }
+}
+
+/// Whether another piece of the same kind could have moved to the square.
+fn ambiguous(variant: Variant, prev: &Position, piece: Piece, from: Square, to: Square) -> bool {
+    variant
+        .squares()
+        .into_iter()
+        .filter(|&sq| sq != from && *prev.piece_at(sq) == Some(piece))
+        .any(|sq| {
+            // Kyoto shogi pieces do not move like the pieces they are stored
+            // as, so name the origin whenever there is a twin.
+            variant == Variant::Kyotoshogi
+                || [false, true].iter().any(|&promote| {
+                    Position::from_sfen(&prev.to_sfen())
+                        .is_ok_and(|mut pos| pos.make_move(Move::Normal { from: sq, to, promote }).is_ok())
+                })
+        })
+}
+
+/// KIF style notation, like `７六歩(77)`, `同　角成(88)` or `５五角打`.
+fn japanese(variant: Variant, prev_move: Option<Move>, written: &Written) -> String {
+    let to = destination(written.m);
+    let mut text = if prev_move.is_some_and(|m| destination(m) == to) {
+        "同\u{3000}".to_owned()
+    } else {
+        format!("{}{}", FILES[usize::from(to.file())], RANKS[usize::from(to.rank())])
+    };
+    text.push_str(japanese_name(variant.face(written.piece).piece_type));
+    match written.m {
+        Move::Normal { from, .. } => {
+            if written.promotion {
+                text.push('成');
+            } else if written.declined {
+                text.push_str("不成");
+            }
+            text.push_str(&format!("({}{})", from.file() + 1, from.rank() + 1));
+        }
+        Move::Drop { .. } => text.push('打'),
+    }
+    text
+}
+
+fn japanese_name(piece_type: PieceType) -> &'static str {
+    match piece_type {
+        PieceType::Pawn => "歩",
+        PieceType::Lance => "香",
+        PieceType::Knight => "桂",
+        PieceType::Silver => "銀",
+        PieceType::Gold => "金",
+        PieceType::Bishop => "角",
+        PieceType::Rook => "飛",
+        PieceType::King => "玉",
+        PieceType::ProPawn => "と",
+        PieceType::ProLance => "成香",
+        PieceType::ProKnight => "成桂",
+        PieceType::ProSilver => "成銀",
+        PieceType::ProBishop => "馬",
+        PieceType::ProRook => "龍",
+    }
+}
+
+/// Characters that Japanese notation writes besides ASCII.
+pub fn japanese_glyphs() -> impl Iterator<Item = char> {
+    FILES
+        .into_iter()
+        .chain(RANKS)
+        .chain(PieceType::iter().flat_map(|piece_type| japanese_name(piece_type).chars()))
+        .chain("同\u{3000}打不成".chars())
+}
@@ -0,0 +1,106 @@
+use std::io::{self, Write};
+
+const SIGNATURE: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+fn chunk<W: Write>(w: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    let mut crc = crc32fast::Hasher::new();
+    crc.update(kind);
+    crc.update(data);
+
+    w.write_all(&(data.len() as u32).to_be_bytes())?;
+    w.write_all(kind)?;
+    w.write_all(data)?;
+    w.write_all(&crc.finalize().to_be_bytes())
+}
+
+/// Writes the signature, an 8 bit indexed color header and the palette.
+pub fn write_header<W: Write>(
+    w: &mut W,
+    width: usize,
+    height: usize,
+    palette: &[u8],
+    transparent: Option<u8>,
+) -> io::Result<()> {
+    w.write_all(&SIGNATURE)?;
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    header.extend_from_slice(&[8, 3, 0, 0, 0]); // depth, indexed color, compression, filter, interlace
+    chunk(w, b"IHDR", &header)?;
+
+    chunk(w, b"PLTE", palette)?;
+
+    if let Some(idx) = transparent {
+        let mut alpha = vec![255; usize::from(idx)];
+        alpha.push(0);
+        chunk(w, b"tRNS", &alpha)?;
+    }
+
+    Ok(())
+}
+
+/// Marks the image as an animation with the given number of frames,
+/// looping forever.
+pub fn write_animation_control<W: Write>(w: &mut W, num_frames: usize) -> io::Result<()> {
+    let mut control = Vec::with_capacity(8);
+    control.extend_from_slice(&(num_frames as u32).to_be_bytes());
+    control.extend_from_slice(&0u32.to_be_bytes()); // num plays
+    chunk(w, b"acTL", &control)
+}
+
+pub struct FrameControl {
+    pub sequence: u32,
+    pub left: usize,
+    pub top: usize,
+    pub width: usize,
+    pub height: usize,
+    pub delay: u16,
+    /// Blend transparent pixels over the previous frame, instead of
+    /// replacing it.
+    pub blend: bool,
+}
+
+pub fn write_frame_control<W: Write>(w: &mut W, frame: FrameControl) -> io::Result<()> {
+    let mut control = Vec::with_capacity(26);
+    control.extend_from_slice(&frame.sequence.to_be_bytes());
+    control.extend_from_slice(&(frame.width as u32).to_be_bytes());
+    control.extend_from_slice(&(frame.height as u32).to_be_bytes());
+    control.extend_from_slice(&(frame.left as u32).to_be_bytes());
+    control.extend_from_slice(&(frame.top as u32).to_be_bytes());
+    control.extend_from_slice(&frame.delay.to_be_bytes());
+    control.extend_from_slice(&100u16.to_be_bytes()); // delay in centiseconds
+    control.push(0); // dispose op: none
+    control.push(if frame.blend { 1 } else { 0 });
+    chunk(w, b"fcTL", &control)
+}
+
+pub fn write_comment<W: Write>(w: &mut W, comment: &str) -> io::Result<()> {
+    // International text, uncompressed, without language tag.
+    let mut text = b"Comment\0\0\0\0\0".to_vec();
+    text.extend_from_slice(comment.as_bytes());
+    chunk(w, b"iTXt", &text)
+}
+
+fn compress(width: usize, data: &[u8]) -> Vec<u8> {
+    let mut filtered = Vec::with_capacity(data.len() + data.len() / width);
+    for row in data.chunks(width) {
+        filtered.push(0); // no filter
+        filtered.extend_from_slice(row);
+    }
+    miniz_oxide::deflate::compress_to_vec_zlib(&filtered, 6)
+}
+
+pub fn write_image<W: Write>(w: &mut W, width: usize, data: &[u8]) -> io::Result<()> {
+    chunk(w, b"IDAT", &compress(width, data))
+}
+
+pub fn write_frame_data<W: Write>(w: &mut W, sequence: u32, width: usize, data: &[u8]) -> io::Result<()> {
+    let mut frame = sequence.to_be_bytes().to_vec();
+    frame.extend_from_slice(&compress(width, data));
+    chunk(w, b"fdAT", &frame)
+}
+
+pub fn write_end<W: Write>(w: &mut W) -> io::Result<()> {
+    chunk(w, b"IEND", &[])
+}
@@ -2,11 +2,18 @@ use bytes::{BufMut, Bytes, BytesMut};
 use gift::{block, Encoder};
 use ndarray::{s, ArrayViewMut2};
 use rusttype::Scale;
-use shogi::{bitboard::Factory, Bitboard, Color, Move, Piece, PieceType, Position, Square};
-use std::{iter::FusedIterator, vec};
+use shogi::{Bitboard, Color, Move, Piece, PieceType, Position, Square};
+use std::{iter::FusedIterator, sync::Arc, vec};
 
-use crate::api::{Comment, Orientation, PlayerName, RequestBody, RequestParams};
+use crate::api::{
+    Brush, CheckSquare, Comment, Coords, Eval, Format, GameResult, Notation, Orientation, PlayerInfo, PlayerName,
+    Players, RequestBody, RequestFrame, RequestParams, Shape, ShapeOrig, Shapes,
+};
+use crate::error::{RequestError, MAX_FRAMES};
+use crate::notation;
+use crate::png;
 use crate::theme::{SpriteHandKey, SpriteKey, Theme};
+use crate::variant::Variant;
 
 enum RenderState {
     Preamble,
@@ -17,32 +24,215 @@ enum RenderState {
 struct PlayerBars {
     black: PlayerName,
     white: PlayerName,
+    players: Players,
 }
 
 impl PlayerBars {
-    fn from(black: Option<PlayerName>, white: Option<PlayerName>) -> Option<PlayerBars> {
+    fn from(black: Option<PlayerName>, white: Option<PlayerName>, players: Players) -> Option<PlayerBars> {
         if black.is_some() || white.is_some() {
             Some(PlayerBars {
                 black: black.unwrap_or_default(),
                 white: white.unwrap_or_default(),
+                players,
             })
         } else {
             None
         }
     }
+
+    fn get(&self, color: Color) -> (&str, PlayerInfo) {
+        let name = match color {
+            Color::Black => &self.black,
+            Color::White => &self.white,
+        };
+        (name.as_str(), self.players.info(color))
+    }
 }
 
 #[derive(Default, Debug)]
-struct RenderFrame {
-    sfen: Position,
-    checked: Bitboard,
-    highlighted: Bitboard,
-    delay: Option<u16>,
+pub struct RenderFrame {
+    pub sfen: Position,
+    pub checked: Bitboard,
+    pub highlighted: Bitboard,
+    pub shapes: Vec<Shape>,
+    pub delay: Option<u16>,
+    pub black_clock: Option<Clock>,
+    pub white_clock: Option<Clock>,
+    pub result: Option<GameResult>,
+    pub last_move: Option<Move>,
+    pub caption: Option<String>,
+    pub eval: Option<Eval>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Clock {
+    /// Main time left, in centiseconds.
+    time: u32,
+    /// Byoyomi left, in centiseconds.
+    byoyomi: Option<u32>,
+}
+
+impl Clock {
+    pub fn new(time: Option<u32>, byoyomi: Option<u32>) -> Option<Clock> {
+        if time.is_none() && byoyomi.is_none() {
+            None
+        } else {
+            Some(Clock {
+                time: time.unwrap_or(0),
+                byoyomi,
+            })
+        }
+    }
+
+    /// Time as shown on the clock. Once the main time is used up, the
+    /// byoyomi counts down instead.
+    fn text(&self) -> String {
+        let centis = match self.byoyomi {
+            Some(byoyomi) if self.time == 0 => byoyomi,
+            _ => self.time,
+        };
+        let (hours, minutes, seconds) = (centis / 360_000, centis / 6000 % 60, centis / 100 % 60);
+        if hours > 0 {
+            format!("{}:{:02}:{:02}", hours, minutes, seconds)
+        } else if centis < 1000 {
+            format!("{}:{:02}.{}", minutes, seconds, centis / 10 % 10)
+        } else {
+            format!("{}:{:02}", minutes, seconds)
+        }
+    }
 }
 
 impl RenderFrame {
-    fn diff(&self, prev: &RenderFrame) -> Bitboard {
-        (&prev.checked ^ &self.checked)
+    pub fn new(
+        variant: Variant,
+        sfen: Option<&str>,
+        last_move: Option<&str>,
+        check: CheckSquare,
+        shapes: Shapes,
+        delay: Option<u16>,
+    ) -> Result<RenderFrame, RequestError> {
+        let sfen = variant.parse_sfen(sfen.unwrap_or(variant.starting_position()))?;
+
+        let last_move = match last_move {
+            Some(usi) => {
+                let m = variant
+                    .parse_move(usi)
+                    .ok_or_else(|| RequestError::InvalidMove(usi.to_owned()))?;
+                let to = match m {
+                    Move::Normal { from, to, .. } => {
+                        variant.check_square(from)?;
+                        variant.check_square(to)?
+                    }
+                    Move::Drop { to, .. } => variant.check_square(to)?,
+                };
+                if sfen.piece_at(to).is_none() {
+                    return Err(RequestError::InconsistentLastMove(usi.to_owned()));
+                }
+                Some(m)
+            }
+            None => None,
+        };
+
+        for shape in &shapes.0 {
+            match *shape {
+                Shape::Circle { square, .. } => {
+                    variant.check_square(square)?;
+                }
+                Shape::Arrow { orig, dest, .. } => {
+                    match orig {
+                        ShapeOrig::Square(square) => variant.check_square(square).map(|_| ())?,
+                        ShapeOrig::Hand(piece) => variant.check_hand_piece(piece).map(|_| ())?,
+                    }
+                    variant.check_square(dest)?;
+                }
+            }
+        }
+
+        let checked = match check.to_square(variant, &sfen) {
+            Some(sq) => Bitboard::from_square(variant.check_square(sq)?),
+            None => Bitboard::empty(),
+        };
+
+        Ok(RenderFrame {
+            highlighted: highlight_move(last_move),
+            checked,
+            sfen,
+            shapes: shapes.0,
+            delay,
+            black_clock: None,
+            white_clock: None,
+            result: None,
+            last_move,
+            caption: None,
+            eval: None,
+        })
+    }
+
+    /// The clock of the player, and whether it is running.
+    fn clock(&self, color: Color) -> Option<(Clock, bool)> {
+        let clock = match color {
+            Color::Black => self.black_clock,
+            Color::White => self.white_clock,
+        };
+        clock.map(|clock| (clock, self.sfen.side_to_move() == color))
+    }
+
+    /// Squares and hand pieces covered by any of the shapes.
+    fn shape_tiles(
+        &self,
+        theme: &Theme,
+        variant: Variant,
+        coords: Coords,
+        orientation: Orientation,
+    ) -> (Bitboard, Vec<Piece>) {
+        let outlines: Vec<Outline> = self
+            .shapes
+            .iter()
+            .map(|shape| Outline::new(shape, theme, variant, coords, orientation))
+            .collect();
+
+        let mut squares = Bitboard::empty();
+        for sq in variant.squares() {
+            let (left, top) = square_pos(theme, variant, orientation, sq);
+            if outlines
+                .iter()
+                .any(|o| o.touches(theme, left, top, theme.square_width(), theme.square_height()))
+            {
+                squares = squares | Bitboard::from_square(sq);
+            }
+        }
+
+        let mut pieces = Vec::new();
+        for piece in variant.hand_pieces() {
+            let (left, top) = hand_band_pos(theme, variant, coords, orientation, piece);
+            if outlines
+                .iter()
+                .any(|o| o.touches(theme, left, top, theme.hand_width(), theme.square_height()))
+            {
+                pieces.push(piece);
+            }
+        }
+
+        (squares, pieces)
+    }
+
+    fn diff(
+        &self,
+        prev: &RenderFrame,
+        theme: &Theme,
+        variant: Variant,
+        coords: Coords,
+        orientation: Orientation,
+    ) -> Bitboard {
+        let shapes = if prev.shapes != self.shapes {
+            prev.shape_tiles(theme, variant, coords, orientation).0
+                | self.shape_tiles(theme, variant, coords, orientation).0
+        } else {
+            Bitboard::empty()
+        };
+
+        shapes
+            | (&prev.checked ^ &self.checked)
             | (&prev.highlighted ^ &self.highlighted)
             | (prev.sfen.player_bb(Color::Black) ^ self.sfen.player_bb(Color::Black))
             | (prev.sfen.piece_bb(PieceType::Pawn) ^ self.sfen.piece_bb(PieceType::Pawn))
@@ -61,10 +251,17 @@ impl RenderFrame {
             | (prev.sfen.piece_bb(PieceType::ProRook) ^ self.sfen.piece_bb(PieceType::ProRook))
     }
 
-    fn hand_diff(&self, prev: &RenderFrame) -> Vec<Piece> {
+    fn hand_diff(
+        &self,
+        prev: &RenderFrame,
+        theme: &Theme,
+        variant: Variant,
+        coords: Coords,
+        orientation: Orientation,
+    ) -> Vec<Piece> {
         let mut t: Vec<Piece> = Vec::new();
 
-        for pt in PieceType::iter().filter(|pt| pt.is_hand_piece()) {
+        for &pt in variant.hand_piece_types() {
             for c in Color::iter() {
                 let piece = Piece {
                     color: c,
@@ -75,258 +272,571 @@ impl RenderFrame {
                 }
             }
         }
+
+        if prev.shapes != self.shapes {
+            let (_, prev_pieces) = prev.shape_tiles(theme, variant, coords, orientation);
+            let (_, pieces) = self.shape_tiles(theme, variant, coords, orientation);
+            for piece in prev_pieces.into_iter().chain(pieces) {
+                if !t.contains(&piece) {
+                    t.push(piece);
+                }
+            }
+        }
         t
     }
 }
 
 pub struct Render {
-    theme: &'static Theme,
+    theme: Arc<Theme>,
     state: RenderState,
     buffer: Vec<u8>,
     comment: Option<Comment>,
     bars: Option<PlayerBars>,
+    variant: Variant,
+    coords: Coords,
     orientation: Orientation,
+    caption: bool,
+    eval: bool,
+    /// Whether player bars leave room for clocks.
+    clocks: bool,
+    options: FrameOptions,
     frames: vec::IntoIter<RenderFrame>,
     kork: bool,
+    format: Format,
+    transparent: bool,
+    sequence: u32,
 }
 
 impl Render {
-    pub fn new_image(theme: &'static Theme, params: RequestParams) -> Render {
+    pub fn new_image(theme: Arc<Theme>, params: RequestParams, format: Format) -> Result<Render, RequestError> {
+        check_glyphs(&theme, params.coords, None)?;
+        let frame = RenderFrame::new(
+            params.variant,
+            params.sfen.as_deref(),
+            params.last_move.as_deref(),
+            params.check,
+            params.shapes,
+            None,
+        )?;
+
         let bars = params.black.is_some() || params.white.is_some();
-        Render {
+        Ok(Render {
+            buffer: vec![
+                0;
+                theme.height(params.variant, bars, params.coords, false)
+                    * theme.width(params.variant, params.coords)
+            ],
             theme,
-            buffer: vec![0; theme.height(bars) * theme.width()],
             state: RenderState::Preamble,
             comment: params.comment,
-            bars: PlayerBars::from(params.black, params.white),
+            bars: PlayerBars::from(params.black, params.white, params.players),
+            variant: params.variant,
+            coords: params.coords,
             orientation: params.orientation,
-            frames: vec![RenderFrame {
-                highlighted: highlight_move(params.last_move),
-                checked: params
-                    .check
-                    .to_square(params.sfen.find_king(params.sfen.side_to_move()))
-                    .map(|sq| Bitboard::from_square(sq))
-                    .unwrap_or(Bitboard::empty()),
-                sfen: params.sfen,
-                delay: None,
-            }]
-            .into_iter(),
+            caption: false,
+            eval: false,
+            clocks: false,
+            options: FrameOptions {
+                variant: params.variant,
+                delay: 0,
+                strict: false,
+                notation: None,
+            },
+            frames: vec![frame].into_iter(),
             kork: false,
+            format,
+            transparent: params.transparent,
+            sequence: 0,
+        })
+    }
+
+    pub fn new_animation(theme: Arc<Theme>, mut params: RequestBody) -> Result<Render, RequestError> {
+        check_glyphs(&theme, params.coords, params.notation)?;
+        if params.frames.is_empty() {
+            return Err(RequestError::EmptyFrames);
+        } else if params.frames.len() > MAX_FRAMES {
+            return Err(RequestError::TooManyFrames);
         }
+
+        let options = FrameOptions::new(&params);
+        let mut frames: Vec<RenderFrame> = Vec::with_capacity(params.frames.len());
+        for frame in std::mem::take(&mut params.frames) {
+            let frame = options.frame(frames.last(), frame)?;
+            frames.push(frame);
+        }
+
+        if let Some(last) = frames.last_mut() {
+            last.result = params.result;
+        }
+
+        let eval = frames.iter().any(|frame| frame.eval.is_some());
+        let clocks = frames
+            .iter()
+            .any(|frame| frame.black_clock.is_some() || frame.white_clock.is_some());
+        Ok(Render::animation(theme, params, options, eval, clocks, frames))
     }
 
-    pub fn new_animation(theme: &'static Theme, params: RequestBody) -> Render {
-        let bars = params.black.is_some() || params.white.is_some();
-        let default_delay = params.delay;
+    /// Starts an animation that is continued with `push` as frames arrive,
+    /// and ended by iterating over the rest. The frames of the request are
+    /// rendered right away. Only GIF can be streamed, as animated PNG
+    /// declares the number of frames upfront.
+    pub fn new_stream(theme: Arc<Theme>, mut params: RequestBody) -> Result<(Render, Vec<Bytes>), RequestError> {
+        check_glyphs(&theme, params.coords, params.notation)?;
+        if params.frames.len() > MAX_FRAMES {
+            return Err(RequestError::TooManyFrames);
+        }
+
+        let options = FrameOptions::new(&params);
+        let eval = params.frames.iter().any(|frame| frame.eval.is_some());
+        let frames = std::mem::take(&mut params.frames);
+        params.format = Some(Format::Gif);
+        // Clocks may only start with a later line.
+        let mut render = Render::animation(theme, params, options, eval, true, Vec::new());
+        let preamble = frames
+            .into_iter()
+            .map(|frame| render.push(frame))
+            .collect::<Result<_, _>>()?;
+        Ok((render, preamble))
+    }
 
+    fn animation(
+        theme: Arc<Theme>,
+        params: RequestBody,
+        options: FrameOptions,
+        eval: bool,
+        clocks: bool,
+        frames: Vec<RenderFrame>,
+    ) -> Render {
+        let bars = params.black.is_some() || params.white.is_some();
         Render {
+            buffer: vec![
+                0;
+                theme.height(params.variant, bars, params.coords, params.notation.is_some())
+                    * (theme.width(params.variant, params.coords) + if eval { theme.eval_width() } else { 0 })
+            ],
             theme,
-            buffer: vec![0; theme.height(bars) * theme.width()],
             state: RenderState::Preamble,
             comment: params.comment,
-            bars: PlayerBars::from(params.black, params.white),
+            bars: PlayerBars::from(params.black, params.white, params.players),
+            variant: params.variant,
+            coords: params.coords,
             orientation: params.orientation,
-            frames: params
-                .frames
-                .into_iter()
-                .map(|frame| RenderFrame {
-                    highlighted: highlight_move(frame.last_move),
-                    checked: frame
-                        .check
-                        .to_square(frame.sfen.find_king(frame.sfen.side_to_move()))
-                        .map(|sq| Bitboard::from_square(sq))
-                        .unwrap_or(Bitboard::empty()),
-                    sfen: frame.sfen,
-                    delay: Some(frame.delay.unwrap_or(default_delay)),
-                })
-                .collect::<Vec<_>>()
-                .into_iter(),
+            caption: params.notation.is_some(),
+            eval,
+            clocks,
+            options,
+            frames: frames.into_iter(),
             kork: true,
+            format: params.format.unwrap_or_default(),
+            transparent: false,
+            sequence: 0,
+        }
+    }
+
+    /// Renders a frame following those rendered so far, completing it from
+    /// the previous one like the frames of an animation.
+    pub fn push(&mut self, frame: RequestFrame) -> Result<Bytes, RequestError> {
+        let prev = match self.state {
+            RenderState::Frame(ref prev) => Some(prev),
+            _ => None,
+        };
+        let frame = self.options.frame(prev, frame)?;
+        Ok(self.encode(Some(frame)).expect("render not complete"))
+    }
+}
+
+/// How the frames of an animation are completed from the request.
+#[derive(Copy, Clone)]
+struct FrameOptions {
+    variant: Variant,
+    delay: u16,
+    strict: bool,
+    notation: Option<Notation>,
+}
+
+impl FrameOptions {
+    fn new(params: &RequestBody) -> FrameOptions {
+        FrameOptions {
+            variant: params.variant,
+            delay: params.delay,
+            strict: params.strict,
+            notation: params.notation,
+        }
+    }
+
+    fn frame(self, prev: Option<&RenderFrame>, req: RequestFrame) -> Result<RenderFrame, RequestError> {
+        let variant = self.variant;
+        let mut frame = RenderFrame {
+            black_clock: Clock::new(req.black_clock, req.black_byoyomi),
+            white_clock: Clock::new(req.white_clock, req.white_byoyomi),
+            eval: req.eval,
+            ..RenderFrame::new(
+                variant,
+                req.sfen.as_deref(),
+                req.last_move.as_deref(),
+                req.check,
+                req.shapes,
+                Some(req.delay.unwrap_or(self.delay)),
+            )?
+        };
+
+        if let Some(prev) = prev {
+            match frame.last_move {
+                Some(m) if self.strict && !holds_moved_piece(variant, &prev.sfen, &frame.sfen, m) => {
+                    return Err(RequestError::MismatchedLastMove(variant.move_to_usi(m)));
+                }
+                Some(_) => {}
+                None => {
+                    frame.last_move = infer_move(variant, &prev.sfen, &frame.sfen);
+                    frame.highlighted = highlight_move(frame.last_move);
+                }
+            }
+        }
+
+        if let Some(notation) = self.notation {
+            frame.caption = frame.last_move.map(|m| {
+                notation::write_move(
+                    notation,
+                    variant,
+                    prev.map(|p| &p.sfen),
+                    prev.and_then(|p| p.last_move),
+                    &frame.sfen,
+                    m,
+                )
+            });
+        }
+
+        Ok(frame)
+    }
+}
+
+/// Everything about the image that stays the same from frame to frame.
+#[derive(Copy, Clone)]
+struct Layout {
+    variant: Variant,
+    coords: Coords,
+    orientation: Orientation,
+    bars: bool,
+    caption: bool,
+    eval: bool,
+    /// Background of the player bars and the caption strip.
+    background: u8,
+}
+
+impl Layout {
+    /// Width of the image, including the eval bar right of the hand.
+    fn width(&self, theme: &Theme) -> usize {
+        if self.eval {
+            theme.width(self.variant, self.coords) + theme.eval_width()
+        } else {
+            theme.width(self.variant, self.coords)
         }
     }
 }
 
+impl Render {
+    fn layout(&self) -> Layout {
+        Layout {
+            variant: self.variant,
+            coords: self.coords,
+            orientation: self.orientation,
+            bars: self.bars.is_some(),
+            caption: self.caption,
+            eval: self.eval,
+            background: self.bar_background(),
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.layout().width(&self.theme)
+    }
+
+    fn height(&self) -> usize {
+        self.theme
+            .height(self.variant, self.bars.is_some(), self.coords, self.caption)
+    }
+
+    fn bar_background(&self) -> u8 {
+        if self.transparent {
+            self.theme.transparent_color()
+        } else {
+            self.theme.bar_color()
+        }
+    }
+
+    fn render_first(&mut self, frame: &RenderFrame) {
+        let background = self.bar_background();
+        let board_top = self.theme.board_top(self.bars.is_some(), self.coords);
+        let board_bottom = board_top + self.theme.board_height(self.variant);
+        let right_hand = self.theme.width(self.variant, self.coords) - self.theme.hand_width();
+        let clock_width = if self.clocks {
+            self.width() - (right_hand - self.theme.clock_width())
+        } else {
+            0
+        };
+
+        let mut view = ArrayViewMut2::from_shape((self.height(), self.width()), &mut self.buffer).expect("shape");
+
+        if let Some(ref bars) = self.bars {
+            render_bar(
+                view.slice_mut(s!(..self.theme.bar_height(), ..)),
+                &self.theme,
+                background,
+                bars.get(self.orientation.fold(Color::White, Color::Black)),
+                clock_width,
+            );
+            render_bar(
+                view.slice_mut(s!(board_bottom.., ..)),
+                &self.theme,
+                background,
+                bars.get(self.orientation.fold(Color::Black, Color::White)),
+                clock_width,
+            );
+        }
+
+        if self.coords != Coords::None {
+            render_coords(
+                view.slice_mut(s!(
+                    (board_top - self.theme.coords_margin(self.coords))..board_bottom,
+                    ..
+                )),
+                &self.theme,
+                background,
+                self.variant,
+                self.coords,
+                self.orientation,
+            );
+        }
+
+        render_hand(
+            view.slice_mut(s!(board_top..board_bottom, ..self.theme.hand_width())),
+            &self.theme,
+            true,
+        );
+        render_hand(
+            view.slice_mut(s!(
+                board_top..board_bottom,
+                right_hand..(right_hand + self.theme.hand_width())
+            )),
+            &self.theme,
+            false,
+        );
+
+        let layout = self.layout();
+        render_diff(&mut self.buffer, &self.theme, layout, None, frame);
+    }
+}
+
 impl Iterator for Render {
     type Item = Bytes;
 
     fn next(&mut self) -> Option<Bytes> {
+        let frame = self.frames.next();
+        self.encode(frame)
+    }
+}
+
+impl FusedIterator for Render {}
+
+impl Render {
+    /// Encodes the frame, or the end of the image if there is none left.
+    fn encode(&mut self, frame: Option<RenderFrame>) -> Option<Bytes> {
         let mut output = BytesMut::new().writer();
         match self.state {
             RenderState::Preamble => {
-                let mut blocks = Encoder::new(&mut output).into_block_enc();
-
-                blocks.encode(block::Header::default()).expect("enc header");
-
-                blocks
-                    .encode(
-                        block::LogicalScreenDesc::default()
-                            .with_screen_height(self.theme.height(self.bars.is_some()) as u16)
-                            .with_screen_width(self.theme.width() as u16)
-                            .with_color_table_config(self.theme.color_table_config()),
-                    )
-                    .expect("enc logical screen desc");
-
-                blocks
-                    .encode(self.theme.global_color_table().clone())
-                    .expect("enc global color table");
-
-                blocks
-                    .encode(block::Application::with_loop_count(0))
-                    .expect("enc application");
+                let frame = frame.unwrap_or_default();
+                self.render_first(&frame);
 
+                let height = self.height();
+                let width = self.width();
                 let comment = self
                     .comment
                     .as_ref()
-                    .map_or("https://github.com/WandererXII/lishogi-gif".as_bytes(), |c| {
-                        c.as_bytes()
-                    });
-                if !comment.is_empty() {
-                    let mut comments = block::Comment::default();
-                    comments.add_comment(comment);
-                    blocks.encode(comments).expect("enc comment");
-                }
-
-                let mut view = ArrayViewMut2::from_shape(
-                    (self.theme.height(self.bars.is_some()), self.theme.width()),
-                    &mut self.buffer,
-                )
-                .expect("shape");
-
-                let mut board_view = if let Some(ref bars) = self.bars {
-                    render_bar(
-                        view.slice_mut(s!(..self.theme.bar_height(), ..)),
-                        self.theme,
-                        self.orientation.fold(&bars.white, &bars.black),
-                    );
-                    render_bar(
-                        view.slice_mut(s!((self.theme.bar_height() + self.theme.board_height()).., ..)),
-                        self.theme,
-                        self.orientation.fold(&bars.black, &bars.white),
-                    );
-                    render_hand(
-                        view.slice_mut(s!(
-                            self.theme.bar_height()..(self.theme.bar_height() + self.theme.board_height()),
-                            ..self.theme.hand_width()
-                        )),
-                        self.theme,
-                        true,
-                    );
-                    render_hand(
-                        view.slice_mut(s!(
-                            self.theme.bar_height()..(self.theme.bar_height() + self.theme.board_height()),
-                            (self.theme.hand_width() + self.theme.board_width())..
-                        )),
-                        self.theme,
-                        false,
-                    );
-                    view.slice_mut(s!(
-                        self.theme.bar_height()..(self.theme.bar_height() + self.theme.board_height()),
-                        ..
-                    ))
+                    .map_or("https://github.com/WandererXII/lishogi-gif", |c| c.as_str());
+                let transparent = if self.transparent {
+                    Some(self.theme.transparent_color())
                 } else {
-                    render_hand(view.slice_mut(s!(.., ..self.theme.hand_width())), self.theme, true);
-                    render_hand(
-                        view.slice_mut(s!(.., (self.theme.hand_width() + self.theme.board_width())..)),
-                        self.theme,
-                        false,
-                    );
-                    view
+                    None
                 };
 
-                let frame = self.frames.next().unwrap_or_default();
-
-                if let Some(delay) = frame.delay {
-                    let mut ctrl = block::GraphicControl::default();
-                    ctrl.set_delay_time_cs(delay);
-                    blocks.encode(ctrl).expect("enc graphic control");
-                }
+                match self.format {
+                    Format::Gif => {
+                        let mut blocks = Encoder::new(&mut output).into_block_enc();
 
-                render_diff(
-                    board_view.as_slice_mut().expect("continguous"),
-                    self.theme,
-                    self.orientation,
-                    None,
-                    &frame,
-                );
-
-                blocks
-                    .encode(
-                        block::ImageDesc::default()
-                            .with_height(self.theme.height(self.bars.is_some()) as u16)
-                            .with_width(self.theme.width() as u16),
-                    )
-                    .expect("enc image desc");
-
-                let mut image_data = block::ImageData::new(self.buffer.len());
-                image_data.data_mut().extend_from_slice(&self.buffer);
-                blocks.encode(image_data).expect("enc image data");
+                        blocks.encode(block::Header::default()).expect("enc header");
 
-                self.state = RenderState::Frame(frame);
-            }
-            RenderState::Frame(ref prev) => {
-                let mut blocks = Encoder::new(&mut output).into_block_enc();
-
-                if let Some(frame) = self.frames.next() {
-                    let mut ctrl = block::GraphicControl::default();
-                    ctrl.set_disposal_method(block::DisposalMethod::Keep);
-                    ctrl.set_transparent_color_idx(self.theme.transparent_color());
-                    if let Some(delay) = frame.delay {
-                        ctrl.set_delay_time_cs(delay);
-                    }
-                    blocks.encode(ctrl).expect("enc graphic control");
+                        blocks
+                            .encode(
+                                block::LogicalScreenDesc::default()
+                                    .with_screen_height(height as u16)
+                                    .with_screen_width(width as u16)
+                                    .with_color_table_config(self.theme.color_table_config()),
+                            )
+                            .expect("enc logical screen desc");
 
-                    let ((left, y), (w, h)) =
-                        render_diff(&mut self.buffer, self.theme, self.orientation, Some(&prev), &frame);
+                        blocks
+                            .encode(self.theme.global_color_table().clone())
+                            .expect("enc global color table");
 
-                    let top = y + if self.bars.is_some() {
-                        self.theme.bar_height()
-                    } else {
-                        0
-                    };
+                        blocks
+                            .encode(block::Application::with_loop_count(0))
+                            .expect("enc application");
 
-                    blocks
-                        .encode(
-                            block::ImageDesc::default()
-                                .with_left(left as u16)
-                                .with_top(top as u16)
-                                .with_height(h as u16)
-                                .with_width(w as u16),
-                        )
-                        .expect("enc image desc");
+                        if !comment.is_empty() {
+                            let mut comments = block::Comment::default();
+                            comments.add_comment(comment.as_bytes());
+                            blocks.encode(comments).expect("enc comment");
+                        }
 
-                    let mut image_data = block::ImageData::new(w * h);
-                    image_data.data_mut().extend_from_slice(&self.buffer[..(w * h)]);
-                    blocks.encode(image_data).expect("enc image data");
+                        if frame.delay.is_some() || transparent.is_some() {
+                            let mut ctrl = block::GraphicControl::default();
+                            if let Some(delay) = frame.delay {
+                                ctrl.set_delay_time_cs(delay);
+                            }
+                            if let Some(idx) = transparent {
+                                ctrl.set_transparent_color_idx(idx);
+                            }
+                            blocks.encode(ctrl).expect("enc graphic control");
+                        }
 
-                    self.state = RenderState::Frame(frame);
-                } else {
-                    // Add a black frame at the end, to work around twitter
-                    // cutting off the last frame.
-                    if self.kork {
-                        let mut ctrl = block::GraphicControl::default();
-                        ctrl.set_disposal_method(block::DisposalMethod::Keep);
-                        ctrl.set_transparent_color_idx(self.theme.transparent_color());
-                        ctrl.set_delay_time_cs(1);
-                        blocks.encode(ctrl).expect("enc graphic control");
-
-                        let height = self.theme.height(self.bars.is_some());
-                        let width = self.theme.width();
                         blocks
                             .encode(
                                 block::ImageDesc::default()
-                                    .with_left(0)
-                                    .with_top(0)
                                     .with_height(height as u16)
                                     .with_width(width as u16),
                             )
                             .expect("enc image desc");
 
-                        let mut image_data = block::ImageData::new(height * width);
-                        image_data.data_mut().resize(height * width, self.theme.bar_color());
+                        let mut image_data = block::ImageData::new(self.buffer.len());
+                        image_data.data_mut().extend_from_slice(&self.buffer);
                         blocks.encode(image_data).expect("enc image data");
                     }
+                    Format::Png => {
+                        // Animation frames are blended over the previous
+                        // frame, so the transparent color is always needed.
+                        let animated = self.frames.len() > 0;
+                        let transparent = if animated {
+                            Some(self.theme.transparent_color())
+                        } else {
+                            transparent
+                        };
+
+                        png::write_header(&mut output, width, height, self.theme.palette(), transparent)
+                            .expect("enc png header");
+                        if !comment.is_empty() {
+                            png::write_comment(&mut output, comment).expect("enc png comment");
+                        }
+                        if animated {
+                            png::write_animation_control(&mut output, 1 + self.frames.len())
+                                .expect("enc png animation control");
+                            png::write_frame_control(
+                                &mut output,
+                                png::FrameControl {
+                                    sequence: self.sequence,
+                                    left: 0,
+                                    top: 0,
+                                    width,
+                                    height,
+                                    delay: frame.delay.unwrap_or(0),
+                                    blend: false,
+                                },
+                            )
+                            .expect("enc png frame control");
+                            self.sequence += 1;
+                        }
+                        png::write_image(&mut output, width, &self.buffer).expect("enc png image");
+                    }
+                }
+
+                self.state = RenderState::Frame(frame);
+            }
+            RenderState::Frame(ref prev) => {
+                if let Some(frame) = frame {
+                    let layout = self.layout();
+                    let ((left, top), (w, h)) = render_diff(&mut self.buffer, &self.theme, layout, Some(prev), &frame);
+
+                    match self.format {
+                        Format::Gif => {
+                            let mut blocks = Encoder::new(&mut output).into_block_enc();
+
+                            let mut ctrl = block::GraphicControl::default();
+                            ctrl.set_disposal_method(block::DisposalMethod::Keep);
+                            ctrl.set_transparent_color_idx(self.theme.transparent_color());
+                            if let Some(delay) = frame.delay {
+                                ctrl.set_delay_time_cs(delay);
+                            }
+                            blocks.encode(ctrl).expect("enc graphic control");
+
+                            blocks
+                                .encode(
+                                    block::ImageDesc::default()
+                                        .with_left(left as u16)
+                                        .with_top(top as u16)
+                                        .with_height(h as u16)
+                                        .with_width(w as u16),
+                                )
+                                .expect("enc image desc");
+
+                            let mut image_data = block::ImageData::new(w * h);
+                            image_data.data_mut().extend_from_slice(&self.buffer[..(w * h)]);
+                            blocks.encode(image_data).expect("enc image data");
+                        }
+                        Format::Png => {
+                            png::write_frame_control(
+                                &mut output,
+                                png::FrameControl {
+                                    sequence: self.sequence,
+                                    left,
+                                    top,
+                                    width: w,
+                                    height: h,
+                                    delay: frame.delay.unwrap_or(0),
+                                    blend: true,
+                                },
+                            )
+                            .expect("enc png frame control");
+                            png::write_frame_data(&mut output, self.sequence + 1, w, &self.buffer[..(w * h)])
+                                .expect("enc png frame data");
+                            self.sequence += 2;
+                        }
+                    }
 
-                    blocks.encode(block::Trailer::default()).expect("enc trailer");
+                    self.state = RenderState::Frame(frame);
+                } else {
+                    match self.format {
+                        Format::Gif => {
+                            let mut blocks = Encoder::new(&mut output).into_block_enc();
+
+                            // Add a black frame at the end, to work around twitter
+                            // cutting off the last frame.
+                            if self.kork {
+                                let mut ctrl = block::GraphicControl::default();
+                                ctrl.set_disposal_method(block::DisposalMethod::Keep);
+                                ctrl.set_transparent_color_idx(self.theme.transparent_color());
+                                ctrl.set_delay_time_cs(1);
+                                blocks.encode(ctrl).expect("enc graphic control");
+
+                                let height = self.height();
+                                let width = self.width();
+                                blocks
+                                    .encode(
+                                        block::ImageDesc::default()
+                                            .with_left(0)
+                                            .with_top(0)
+                                            .with_height(height as u16)
+                                            .with_width(width as u16),
+                                    )
+                                    .expect("enc image desc");
+
+                                let mut image_data = block::ImageData::new(height * width);
+                                image_data.data_mut().resize(height * width, self.theme.bar_color());
+                                blocks.encode(image_data).expect("enc image data");
+                            }
+
+                            blocks.encode(block::Trailer::default()).expect("enc trailer");
+                        }
+                        Format::Png => png::write_end(&mut output).expect("enc png end"),
+                    }
                     self.state = RenderState::Complete;
                 }
             }
@@ -336,80 +846,143 @@ impl Iterator for Render {
     }
 }
 
-impl FusedIterator for Render {}
-
 fn render_diff(
     buffer: &mut [u8],
     theme: &Theme,
-    orientation: Orientation,
+    layout: Layout,
     prev: Option<&RenderFrame>,
     frame: &RenderFrame,
 ) -> ((usize, usize), (usize, usize)) {
-    let diff = prev.map_or(Factory::all(), |p| p.diff(frame));
+    let Layout {
+        variant,
+        coords,
+        orientation,
+        bars,
+        caption,
+        eval,
+        background,
+    } = layout;
 
-    let hand_diff: Vec<Piece> = prev.map_or(
-        Color::iter()
-            .flat_map(|c| {
-                PieceType::iter()
-                    .filter(|pt| pt.is_hand_piece())
-                    .map(|pt| Piece {
-                        piece_type: pt,
-                        color: c,
-                    })
-                    .collect::<Vec<Piece>>()
-            })
-            .collect(),
-        |p| p.hand_diff(frame),
-    );
+    let diff = prev.map_or(variant.squares(), |p| {
+        p.diff(frame, theme, variant, coords, orientation)
+    });
+
+    let hand_diff: Vec<Piece> = prev.map_or(variant.hand_pieces(), |p| {
+        p.hand_diff(frame, theme, variant, coords, orientation)
+    });
 
     let hand_left = hand_diff.iter().any(|p| !orientation.eq_color(p.color));
     let hand_right = hand_diff.iter().any(|p| orientation.eq_color(p.color));
 
-    let x_min = if hand_left {
+    let mut x_min = if hand_left {
         0
     } else {
         diff.into_iter()
-            .map(|sq| orientation.x(sq) * theme.square_width())
+            .map(|sq| orientation.x(variant, sq) * theme.square_width())
             .min()
             .unwrap_or(0)
             + theme.hand_width()
     };
-    let x_max = if hand_right {
-        theme.width()
+    let mut x_max = if hand_right {
+        theme.width(variant, coords)
     } else {
         diff.into_iter()
-            .map(|sq| orientation.x(sq) * theme.square_width())
+            .map(|sq| orientation.x(variant, sq) * theme.square_width())
             .max()
             .unwrap_or(0)
             + theme.hand_width()
             + theme.square_width()
     };
 
-    let y_min = std::cmp::min(
+    let mut y_min = std::cmp::min(
         hand_diff
             .iter()
-            .map(|p| orientation.hand_y(*p) * theme.square_height())
+            .map(|p| orientation.hand_y(variant, *p) * theme.square_height())
             .min()
             .unwrap_or(9),
         diff.into_iter()
-            .map(|sq| orientation.y(sq) * theme.square_height())
+            .map(|sq| orientation.y(variant, sq) * theme.square_height())
             .min()
             .unwrap_or(0),
     );
-    let y_max = std::cmp::max(
+    let mut y_max = std::cmp::max(
         hand_diff
             .iter()
-            .map(|p| orientation.hand_y(*p) * theme.square_height())
+            .map(|p| orientation.hand_y(variant, *p) * theme.square_height())
             .max()
             .unwrap_or(0)
             + theme.square_height(),
         diff.into_iter()
-            .map(|sq| orientation.y(sq) * theme.square_height())
+            .map(|sq| orientation.y(variant, sq) * theme.square_height())
             .max()
             .unwrap_or(0)
             + theme.square_height(),
     );
 
+    // From here on, rows are counted from the top of the image, including
+    // the player bars.
+    let board_top = theme.board_top(bars, coords);
+    y_min += board_top;
+    y_max += board_top;
+
+    let clock_diff: Vec<Color> = if bars {
+        Color::iter()
+            .filter(|&c| prev.map_or(frame.clock(c).is_some(), |p| p.clock(c) != frame.clock(c)))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let clock_left = theme.width(variant, coords) - theme.hand_width() - theme.clock_width();
+    for &c in &clock_diff {
+        x_min = std::cmp::min(x_min, clock_left);
+        x_max = theme.width(variant, coords);
+        if orientation.eq_color(c) {
+            y_max = board_top + theme.board_height(variant) + theme.bar_height();
+        } else {
+            y_min = 0;
+        }
+    }
+
+    let banner = match prev {
+        Some(p) if p.result == frame.result => None,
+        _ => frame.result,
+    };
+    let banner_top = board_top + (theme.board_height(variant) - theme.bar_height()) / 2;
+    if banner.is_some() {
+        x_min = std::cmp::min(x_min, theme.hand_width());
+        x_max = std::cmp::max(x_max, theme.hand_width() + theme.board_width(variant));
+        y_min = std::cmp::min(y_min, banner_top);
+        y_max = std::cmp::max(y_max, banner_top + theme.bar_height());
+    }
+
+    let caption_diff = caption
+        && match prev {
+            Some(p) => p.caption != frame.caption,
+            None => true,
+        };
+    let caption_top = theme.height(variant, bars, coords, false);
+    if caption_diff {
+        x_min = 0;
+        x_max = layout.width(theme);
+        y_max = caption_top + theme.bar_height();
+    }
+
+    let eval_diff = eval
+        && match prev {
+            Some(p) => p.eval != frame.eval,
+            None => true,
+        };
+    if eval_diff {
+        x_max = layout.width(theme);
+        y_min = std::cmp::min(y_min, board_top);
+        y_max = std::cmp::max(y_max, board_top + theme.board_height(variant));
+    }
+
+    if prev.is_none() {
+        y_min = 0;
+        y_max = theme.height(variant, bars, coords, caption);
+    }
+
     let width = x_max - x_min;
     let height = y_max - y_min;
 
@@ -419,22 +992,24 @@ fn render_diff(
         view.fill(theme.transparent_color());
     }
 
-    let center_squares = Factory::all()
-        .filter(|sq| {
-            (sq.rank() == 2 || sq.rank() == 3 || sq.rank() == 5 || sq.rank() == 6)
-                && (sq.file() == 2 || sq.file() == 3 || sq.file() == 5 || sq.file() == 6)
-        })
-        .collect::<Vec<Square>>();
+    // Squares touching a star point, which is drawn over their corner.
+    let star_points = variant.star_points();
+    let touches_star_point = |x: usize, y: usize| {
+        star_points
+            .iter()
+            .any(|&(sx, sy)| (x + 1 == sx || x == sx) && (y + 1 == sy || y == sy))
+    };
 
     for sq in diff {
         let key = SpriteKey {
             piece: *frame.sfen.piece_at(sq),
+            variant,
             orientation: orientation,
             highlight: frame.highlighted.is_occupied(sq),
             check: frame.checked.is_occupied(sq),
         };
-        let left = theme.hand_width() + orientation.x(sq) * theme.square_width() - x_min;
-        let top = orientation.y(sq) * theme.square_height() - y_min;
+        let left = theme.hand_width() + orientation.x(variant, sq) * theme.square_width() - x_min;
+        let top = board_top + orientation.y(variant, sq) * theme.square_height() - y_min;
 
         view.slice_mut(s!(
             top..(top + theme.square_height()),
@@ -442,13 +1017,14 @@ fn render_diff(
         ))
         .assign(&theme.sprite(key));
 
-        if center_squares.contains(&sq) {
-            let top_circle = if orientation.y(sq) == 2 || orientation.y(sq) == 5 {
+        let (x, y) = (orientation.x(variant, sq), orientation.y(variant, sq));
+        if touches_star_point(x, y) {
+            let top_circle = if star_points.iter().any(|&(_, sy)| y + 1 == sy) {
                 top + theme.square_height() - theme.circle()
             } else {
                 top
             };
-            let left_circle = if orientation.x(sq) == 2 || orientation.x(sq) == 5 {
+            let left_circle = if star_points.iter().any(|&(sx, _)| x + 1 == sx) {
                 left + theme.square_width() - theme.circle()
             } else {
                 left
@@ -465,7 +1041,7 @@ fn render_diff(
         }
     }
 
-    for p in hand_diff {
+    for &p in &hand_diff {
         let nb = std::cmp::min(frame.sfen.hand(p), 99);
 
         let key = SpriteHandKey {
@@ -473,12 +1049,23 @@ fn render_diff(
             orientation: orientation,
             number: nb,
         };
-        let left = if orientation.eq_color(p.color) {
-            width - theme.square_width() - theme.hand_offset() / 2
+        let left = hand_pos(theme, variant, coords, orientation, p).0 - x_min;
+        let top = board_top + orientation.hand_y(variant, p) * theme.square_height() - y_min;
+
+        // Repaint the whole row of the hand, which shapes may cross.
+        let band_left = hand_band_pos(theme, variant, coords, orientation, p).0 - x_min;
+        view.slice_mut(s!(
+            top..(top + theme.square_height()),
+            band_left..(band_left + theme.hand_width())
+        ))
+        .fill(theme.hand_color());
+        let border = if orientation.eq_color(p.color) {
+            band_left
         } else {
-            theme.hand_offset() / 2
+            band_left + theme.hand_width() - 1
         };
-        let top = orientation.hand_y(p) * theme.square_height() - y_min;
+        view.slice_mut(s!(top..(top + theme.square_height()), border..(border + 1)))
+            .fill(theme.circle_color());
 
         // +1 to cut of border
         view.slice_mut(s!(
@@ -489,22 +1076,22 @@ fn render_diff(
 
         if nb > 0 {
             let mut text_color = theme.white_color();
-            let font_size = 28.0 + (if nb < 10 { 2.0 } else { 0.0 });
-            let x_offset = 56;
-            let y_offset = 50 + (if nb > 10 { 2 } else { 0 });
+            let font_size = theme.px(28.0 + (if nb < 10 { 2.0 } else { 0.0 }));
+            let x_offset = theme.px(56.0);
+            let y_offset = theme.px(50.0 + (if nb > 10 { 2.0 } else { 0.0 }));
             let scale = Scale {
                 x: font_size,
                 y: font_size,
             };
             let g_text = nb.to_string();
-            let g_center = (g_text.len() - 1) as f32 * 6.0;
+            let g_center = (g_text.len() - 1) as f32 * theme.px(6.0);
             let v_metrics = theme.font().v_metrics(scale);
             let glyphs = theme.font().layout(
                 g_text.as_str(),
                 scale,
                 rusttype::point(
-                    (x_offset + left) as f32 - g_center,
-                    (y_offset + top) as f32 + v_metrics.ascent,
+                    x_offset + left as f32 - g_center,
+                    y_offset + top as f32 + v_metrics.ascent,
                 ),
             );
 
@@ -531,9 +1118,307 @@ fn render_diff(
         }
     }
 
+    if !frame.shapes.is_empty() {
+        let outlines: Vec<Outline> = frame
+            .shapes
+            .iter()
+            .map(|shape| Outline::new(shape, theme, variant, coords, orientation))
+            .collect();
+
+        let tiles = diff
+            .into_iter()
+            .map(|sq| (square_pos(theme, variant, orientation, sq), theme.square_width()))
+            .chain(hand_diff.iter().map(|p| {
+                (
+                    hand_band_pos(theme, variant, coords, orientation, *p),
+                    theme.hand_width(),
+                )
+            }));
+
+        for ((left, top), tile_width) in tiles {
+            for outline in outlines
+                .iter()
+                .filter(|o| o.touches(theme, left, top, tile_width, theme.square_height()))
+            {
+                let color = theme.brush_color(outline.brush());
+                for y in top..(top + theme.square_height()) {
+                    for x in left..(left + tile_width) {
+                        if outline.contains(theme, x as f32 + 0.5, y as f32 + 0.5) {
+                            view[(board_top + y - y_min, x - x_min)] = color;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    for &c in &clock_diff {
+        let top = if orientation.eq_color(c) {
+            board_top + theme.board_height(variant)
+        } else {
+            0
+        } - y_min;
+        render_clock(
+            view.slice_mut(s!(
+                top..(top + theme.bar_height()),
+                (clock_left - x_min)..(theme.width(variant, coords) - x_min)
+            )),
+            theme,
+            background,
+            frame.clock(c),
+        );
+    }
+
+    if eval_diff {
+        let top = board_top - y_min;
+        let left = theme.width(variant, coords) - x_min;
+        render_eval(
+            view.slice_mut(s!(
+                top..(top + theme.board_height(variant)),
+                left..(left + theme.eval_width())
+            )),
+            theme,
+            background,
+            orientation,
+            frame.eval,
+        );
+    }
+
+    if caption_diff {
+        let top = caption_top - y_min;
+        render_caption(
+            view.slice_mut(s!(top..(top + theme.bar_height()), ..)),
+            theme,
+            background,
+            frame.caption.as_deref(),
+        );
+    }
+
+    if let Some(result) = banner {
+        let top = banner_top - y_min;
+        let left = theme.hand_width() - x_min;
+        render_banner(
+            view.slice_mut(s!(
+                top..(top + theme.bar_height()),
+                left..(left + theme.board_width(variant))
+            )),
+            theme,
+            &result.to_string(),
+        );
+    }
+
     ((x_min, y_min), (width, height))
 }
 
+/// Top left corner of the square, relative to the board row.
+fn square_pos(theme: &Theme, variant: Variant, orientation: Orientation, sq: Square) -> (usize, usize) {
+    (
+        theme.hand_width() + orientation.x(variant, sq) * theme.square_width(),
+        orientation.y(variant, sq) * theme.square_height(),
+    )
+}
+
+/// Top left corner of the hand piece sprite, relative to the board row.
+pub fn hand_pos(
+    theme: &Theme,
+    variant: Variant,
+    coords: Coords,
+    orientation: Orientation,
+    piece: Piece,
+) -> (usize, usize) {
+    let left = if orientation.eq_color(piece.color) {
+        theme.width(variant, coords) - theme.square_width() - theme.hand_offset() / 2
+    } else {
+        theme.hand_offset() / 2
+    };
+    (left, orientation.hand_y(variant, piece) * theme.square_height())
+}
+
+/// Top left corner of the row of the hand holding the piece.
+fn hand_band_pos(
+    theme: &Theme,
+    variant: Variant,
+    coords: Coords,
+    orientation: Orientation,
+    piece: Piece,
+) -> (usize, usize) {
+    let left = if orientation.eq_color(piece.color) {
+        theme.width(variant, coords) - theme.hand_width()
+    } else {
+        0
+    };
+    (left, orientation.hand_y(variant, piece) * theme.square_height())
+}
+
+/// A shape in pixel coordinates, relative to the board row.
+pub enum Outline {
+    Arrow {
+        from: (f32, f32),
+        to: (f32, f32),
+        brush: Brush,
+    },
+    Circle {
+        center: (f32, f32),
+        brush: Brush,
+    },
+}
+
+impl Outline {
+    pub fn new(shape: &Shape, theme: &Theme, variant: Variant, coords: Coords, orientation: Orientation) -> Outline {
+        let center = |(left, top): (usize, usize)| {
+            (
+                (left + theme.square_width() / 2) as f32,
+                (top + theme.square_height() / 2) as f32,
+            )
+        };
+
+        match *shape {
+            Shape::Circle { square, brush } => Outline::Circle {
+                center: center(square_pos(theme, variant, orientation, square)),
+                brush,
+            },
+            Shape::Arrow { orig, dest, brush } => Outline::Arrow {
+                from: center(match orig {
+                    ShapeOrig::Square(sq) => square_pos(theme, variant, orientation, sq),
+                    ShapeOrig::Hand(piece) => hand_pos(theme, variant, coords, orientation, piece),
+                }),
+                to: center(square_pos(theme, variant, orientation, dest)),
+                brush,
+            },
+        }
+    }
+
+    pub fn brush(&self) -> Brush {
+        match *self {
+            Outline::Arrow { brush, .. } => brush,
+            Outline::Circle { brush, .. } => brush,
+        }
+    }
+
+    fn contains(&self, theme: &Theme, x: f32, y: f32) -> bool {
+        match *self {
+            Outline::Arrow { from, to, .. } => {
+                let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+                let len = dx.hypot(dy);
+                let along = ((x - from.0) * dx + (y - from.1) * dy) / len;
+                let across = ((x - from.0) * dy - (y - from.1) * dx).abs() / len;
+                let head = theme.arrow_head_length().min(len);
+                if along < 0.0 || along > len {
+                    false
+                } else if along < len - head {
+                    across <= theme.arrow_width() / 2.0
+                } else {
+                    across <= theme.arrow_head_width() / 2.0 * (len - along) / head
+                }
+            }
+            Outline::Circle { center, .. } => {
+                ((x - center.0).hypot(y - center.1) - theme.shape_circle_radius()).abs()
+                    <= theme.shape_circle_width() / 2.0
+            }
+        }
+    }
+
+    /// Conservatively tests if the shape overlaps a rectangle.
+    fn touches(&self, theme: &Theme, left: usize, top: usize, width: usize, height: usize) -> bool {
+        let (half_w, half_h) = (width as f32 / 2.0, height as f32 / 2.0);
+        let (cx, cy) = (left as f32 + half_w, top as f32 + half_h);
+
+        match *self {
+            Outline::Arrow { from, to, .. } => {
+                // Separating axis test against the bounding box of the arrow.
+                let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+                let len = dx.hypot(dy);
+                let (ux, uy) = (dx / len, dy / len);
+                let (half_len, half_across) = (len / 2.0, theme.arrow_head_width() / 2.0);
+                let (mx, my) = ((from.0 + to.0) / 2.0 - cx, (from.1 + to.1) / 2.0 - cy);
+
+                mx.abs() <= half_w + ux.abs() * half_len + uy.abs() * half_across
+                    && my.abs() <= half_h + uy.abs() * half_len + ux.abs() * half_across
+                    && (mx * ux + my * uy).abs() <= half_len + half_w * ux.abs() + half_h * uy.abs()
+                    && (my * ux - mx * uy).abs() <= half_across + half_w * uy.abs() + half_h * ux.abs()
+            }
+            Outline::Circle { center, .. } => {
+                let dx = ((center.0 - cx).abs() - half_w).max(0.0);
+                let dy = ((center.1 - cy).abs() - half_h).max(0.0);
+                dx.hypot(dy) <= theme.shape_circle_radius() + theme.shape_circle_width() / 2.0
+            }
+        }
+    }
+}
+
+/// Rejects labels and captions that the font of the theme cannot draw. The
+/// embedded font has no kanji, so Japanese needs a theme that brings one.
+fn check_glyphs(theme: &Theme, coords: Coords, notation: Option<Notation>) -> Result<(), RequestError> {
+    if coords == Coords::Japanese && !(0..9).all(|rank| theme.has_glyph(coords.rank_label(rank))) {
+        return Err(RequestError::MissingGlyphs("coordinates"));
+    }
+    if notation == Some(Notation::Japanese) && !notation::japanese_glyphs().all(|c| theme.has_glyph(c)) {
+        return Err(RequestError::MissingGlyphs("notation"));
+    }
+    Ok(())
+}
+
+/// Draws file labels above the board and rank labels to its right. The view
+/// starts at the row of file labels.
+fn render_coords(
+    mut view: ArrayViewMut2<u8>,
+    theme: &Theme,
+    background: u8,
+    variant: Variant,
+    coords: Coords,
+    orientation: Orientation,
+) {
+    let margin = theme.coords_margin(coords);
+    let board_right = theme.hand_width() + theme.board_width(variant);
+    view.slice_mut(s!(..margin, ..)).fill(background);
+    view.slice_mut(s!(margin.., board_right..(board_right + margin)))
+        .fill(background);
+
+    for sq in variant.squares() {
+        if sq.rank() == 0 {
+            let center =
+                theme.hand_width() + orientation.x(variant, sq) * theme.square_width() + theme.square_width() / 2;
+            render_label(&mut view, theme, coords.file_label(sq.file()), center, margin / 2);
+        }
+        if sq.file() == 0 {
+            let center = margin + orientation.y(variant, sq) * theme.square_height() + theme.square_height() / 2;
+            render_label(
+                &mut view,
+                theme,
+                coords.rank_label(sq.rank()),
+                board_right + margin / 2,
+                center,
+            );
+        }
+    }
+}
+
+/// Draws a single character centered on the given pixel.
+fn render_label(view: &mut ArrayViewMut2<u8>, theme: &Theme, label: char, center_x: usize, center_y: usize) {
+    let height = theme.px(24.0);
+    let glyph = theme
+        .font()
+        .glyph(label)
+        .scaled(Scale { x: height, y: height })
+        .positioned(rusttype::point(0.0, 0.0));
+    if let Some(bb) = glyph.pixel_bounding_box() {
+        let left = center_x as i32 - bb.width() / 2;
+        let top = center_y as i32 - bb.height() / 2;
+        glyph.draw(|x, y, intensity| {
+            let x = left + x as i32;
+            let y = top + y as i32;
+            // Poor man's anti-aliasing.
+            if 0 <= x && x < view.ncols() as i32 && 0 <= y && y < view.nrows() as i32 && intensity >= 0.01 {
+                view[(y as usize, x as usize)] = if intensity < 0.5 {
+                    theme.med_text_color()
+                } else {
+                    theme.text_color()
+                };
+            }
+        });
+    }
+}
+
 fn render_hand(mut view: ArrayViewMut2<u8>, theme: &Theme, left: bool) {
     view.fill(theme.hand_color());
     view.slice_mut(s!(
@@ -547,52 +1432,284 @@ fn render_hand(mut view: ArrayViewMut2<u8>, theme: &Theme, left: bool) {
     .fill(theme.circle_color());
 }
 
-fn render_bar(mut view: ArrayViewMut2<u8>, theme: &Theme, player_name: &str) {
-    view.fill(theme.bar_color());
+/// Draws the player bar, keeping `clock_width` at its right end free for
+/// the clock.
+fn render_bar(
+    mut view: ArrayViewMut2<u8>,
+    theme: &Theme,
+    background: u8,
+    (player_name, info): (&str, PlayerInfo),
+    clock_width: usize,
+) {
+    view.fill(background);
+    let right = view.ncols() - clock_width;
+    let mut view = view.slice_mut(s!(.., ..right));
 
-    let mut text_color = theme.text_color();
-    if player_name.starts_with("BOT ") {
-        text_color = theme.bot_color();
-    } else {
-        for title in &["GM ", "BOT "] {
-            if player_name.starts_with(title) {
-                text_color = theme.gold_color();
-                break;
-            }
-        }
+    let gap = theme.px(10.0);
+    let center = view.nrows() as f32 / 2.0;
+    let mut x = gap + theme.hand_width() as f32;
+
+    if let Some(online) = info.online {
+        let radius = theme.px(7.0);
+        let color = if online {
+            theme.brush_color(Brush::Green)
+        } else {
+            theme.med_text_color()
+        };
+        fill_marker(&mut view, x + radius, center, radius, color, |dx, dy| {
+            dx * dx + dy * dy <= 1.0
+        });
+        x += 2.0 * radius + gap;
+    }
+
+    if info.patron {
+        let radius = theme.px(9.0);
+        fill_marker(&mut view, x + radius, center, radius, theme.gold_color(), |dx, dy| {
+            dx.abs() + dy.abs() <= 1.0
+        });
+        x += 2.0 * radius + gap;
+    }
+
+    if let Some(title) = info.title {
+        let color = if info.is_bot() {
+            theme.bot_color()
+        } else {
+            theme.gold_color()
+        };
+        x = render_text(&mut view, theme, title.as_str(), x, color) + gap;
+    }
+
+    x = render_text(&mut view, theme, player_name, x, theme.text_color());
+
+    if let Some(rating) = info.rating {
+        x = render_text(&mut view, theme, &rating.to_string(), x + gap, theme.med_text_color());
     }
 
-    let height = 40.0;
-    let padding = 10.0;
+    if let Some(diff) = info.rating_diff {
+        let color = match diff {
+            d if d > 0 => theme.brush_color(Brush::Green),
+            d if d < 0 => theme.brush_color(Brush::Red),
+            _ => theme.med_text_color(),
+        };
+        x = render_text(&mut view, theme, &format!("{:+}", diff), x + gap, color);
+    }
+
+    if let Some(flag) = info.flag {
+        render_text(&mut view, theme, flag.as_str(), x + gap, theme.med_text_color());
+    }
+}
+
+/// Draws a line of text at the size used in the player bars, starting at
+/// `x`. Glyphs that do not fit the view are left out. Returns where the next
+/// piece of text can start.
+fn render_text(view: &mut ArrayViewMut2<u8>, theme: &Theme, text: &str, x: f32, color: u8) -> f32 {
+    let height = theme.px(40.0);
+    let padding = theme.px(10.0);
     let scale = Scale { x: height, y: height };
 
     let v_metrics = theme.font().v_metrics(scale);
-    let glyphs = theme.font().layout(
-        player_name,
-        scale,
-        rusttype::point(padding + theme.hand_width() as f32, padding + v_metrics.ascent),
-    );
-
-    for g in glyphs {
+    let mut end = x;
+    for g in theme
+        .font()
+        .layout(text, scale, rusttype::point(x, padding + v_metrics.ascent))
+    {
+        end = g.position().x + g.unpositioned().h_metrics().advance_width;
+        if end > view.ncols() as f32 {
+            break;
+        }
         if let Some(bb) = g.pixel_bounding_box() {
             g.draw(|left, top, intensity| {
                 let left = left as i32 + bb.min.x;
                 let top = top as i32 + bb.min.y;
-                if 0 <= left && left < theme.width() as i32 && 0 <= top && top < theme.bar_height() as i32 {
-                    // Poor man's anti-aliasing.
-                    if intensity < 0.01 {
-                        return;
-                    } else if intensity < 0.5 && text_color == theme.text_color() {
-                        view[(top as usize, left as usize)] = theme.med_text_color();
+                // Poor man's anti-aliasing.
+                if 0 <= left && left < view.ncols() as i32 && 0 <= top && top < view.nrows() as i32 && intensity >= 0.01
+                {
+                    view[(top as usize, left as usize)] = if intensity < 0.5 && color == theme.text_color() {
+                        theme.med_text_color()
                     } else {
-                        view[(top as usize, left as usize)] = text_color;
-                    }
+                        color
+                    };
                 }
             });
-        } else {
-            text_color = theme.text_color();
         }
     }
+    end
+}
+
+/// Width of a line of text at the size used in the player bars.
+fn text_width(theme: &Theme, text: &str) -> f32 {
+    let height = theme.px(40.0);
+    theme
+        .font()
+        .layout(text, Scale { x: height, y: height }, rusttype::point(0.0, 0.0))
+        .last()
+        .map_or(0.0, |g| g.position().x + g.unpositioned().h_metrics().advance_width)
+}
+
+/// Fills the eval bar with the share of sente on its side of the board.
+fn render_eval(
+    mut view: ArrayViewMut2<u8>,
+    theme: &Theme,
+    background: u8,
+    orientation: Orientation,
+    eval: Option<Eval>,
+) {
+    view.fill(background);
+    let eval = match eval {
+        Some(eval) => eval,
+        None => return,
+    };
+
+    let height = view.nrows();
+    let black = (eval.black_share() * height as f32).round() as usize;
+    let rows = if orientation.eq_color(Color::Black) {
+        (height - black)..height
+    } else {
+        0..black
+    };
+    view.slice_mut(s!(rows, ..)).fill(theme.text_color());
+
+    // Mark the middle, where the position is even.
+    view.slice_mut(s!((height / 2)..(height / 2 + 1), ..))
+        .fill(theme.med_text_color());
+}
+
+/// Draws the move caption centered on its strip.
+fn render_caption(mut view: ArrayViewMut2<u8>, theme: &Theme, background: u8, text: Option<&str>) {
+    view.fill(background);
+    if let Some(text) = text {
+        let left = (view.ncols() as f32 - text_width(theme, text)) / 2.0;
+        render_text(&mut view, theme, text, left.max(0.0), theme.text_color());
+    }
+}
+
+/// Draws the game result centered on a band across the board.
+fn render_banner(mut view: ArrayViewMut2<u8>, theme: &Theme, text: &str) {
+    view.fill(theme.bar_color());
+    let left = (view.ncols() as f32 - text_width(theme, text)) / 2.0;
+    render_text(&mut view, theme, text, left.max(0.0), theme.text_color());
+}
+
+/// Fills the pixels around a center point for which `inside` holds, given
+/// offsets scaled to the unit square.
+fn fill_marker(
+    view: &mut ArrayViewMut2<u8>,
+    cx: f32,
+    cy: f32,
+    radius: f32,
+    color: u8,
+    inside: impl Fn(f32, f32) -> bool,
+) {
+    let top = (cy - radius).floor().max(0.0) as usize;
+    let bottom = ((cy + radius).ceil() as usize).min(view.nrows());
+    let left = (cx - radius).floor().max(0.0) as usize;
+    let right = ((cx + radius).ceil() as usize).min(view.ncols());
+    for y in top..bottom {
+        for x in left..right {
+            let dx = (x as f32 + 0.5 - cx) / radius;
+            let dy = (y as f32 + 0.5 - cy) / radius;
+            if inside(dx, dy) {
+                view[(y, x)] = color;
+            }
+        }
+    }
+}
+
+/// Draws the clock at the right end of the bar, aligned with the edge of
+/// the board. The running clock is bright, the other one dimmed.
+fn render_clock(mut view: ArrayViewMut2<u8>, theme: &Theme, background: u8, clock: Option<(Clock, bool)>) {
+    view.fill(background);
+
+    let (clock, running) = match clock {
+        Some(clock) => clock,
+        None => return,
+    };
+    let text_color = if running {
+        theme.text_color()
+    } else {
+        theme.med_text_color()
+    };
+
+    let text = clock.text();
+    let right = (view.ncols() - theme.hand_width()) as f32 - theme.px(10.0);
+    render_text(&mut view, theme, &text, right - text_width(theme, &text), text_color);
+}
+
+/// Finds the move leading from `prev` to `pos`, if they are exactly one
+/// move apart, by comparing the boards and hands.
+fn infer_move(variant: Variant, prev: &Position, pos: &Position) -> Option<Move> {
+    let mover = prev.side_to_move();
+    let mut from = None;
+    let mut to = None;
+    for sq in variant.squares() {
+        let (before, after) = (*prev.piece_at(sq), *pos.piece_at(sq));
+        if before == after {
+            continue;
+        }
+        match (before, after) {
+            (Some(before), None) if before.color == mover && from.is_none() => from = Some((sq, before)),
+            (before, Some(after))
+                if after.color == mover && to.is_none() && !before.is_some_and(|p| p.color == mover) =>
+            {
+                to = Some((sq, after))
+            }
+            _ => return None,
+        }
+    }
+
+    let m = match (from, to?) {
+        (Some((from, before)), (to, after)) => Move::Normal {
+            from,
+            to,
+            promote: !before.piece_type.is_promoted() && after.piece_type.is_promoted(),
+        },
+        (None, (to, after)) => Move::Drop {
+            to,
+            piece_type: after.piece_type,
+        },
+    };
+
+    // Replay the move to make sure the hands agree as well.
+    let mut next = variant.parse_sfen(&variant.to_sfen(prev)).ok()?;
+    if !variant.make_move(&mut next, m) {
+        return None;
+    }
+    let position = |sfen: &str| sfen.split(' ').take(3).collect::<Vec<_>>().join(" ");
+    if position(&variant.to_sfen(&next)) == position(&variant.to_sfen(pos)) {
+        Some(m)
+    } else {
+        None
+    }
+}
+
+/// Whether the destination of the move holds the piece that made it, as
+/// it was in `prev`, promoted or flipped over where appropriate.
+fn holds_moved_piece(variant: Variant, prev: &Position, pos: &Position, m: Move) -> bool {
+    let mover = pos.side_to_move().flip();
+    let (to, before) = match m {
+        Move::Normal { from, to, .. } => match *prev.piece_at(from) {
+            Some(before) if before.color == mover => (to, before),
+            _ => return false,
+        },
+        Move::Drop { to, piece_type } => (
+            to,
+            Piece {
+                piece_type,
+                color: mover,
+            },
+        ),
+    };
+    let after = match *pos.piece_at(to) {
+        Some(after) => after,
+        None => return false,
+    };
+    match m {
+        Move::Normal { .. } if variant == Variant::Kyotoshogi => {
+            before.promote() == Some(after) || before.unpromote() == Some(after)
+        }
+        Move::Normal { promote: true, .. } => before.promote() == Some(after),
+        _ => before == after,
+    }
 }
 
 fn highlight_move(m: Option<Move>) -> Bitboard {
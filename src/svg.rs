@@ -0,0 +1,489 @@
+use rusttype::Scale;
+use shogi::{Color, Piece, PieceType};
+use std::fmt::Write;
+
+use crate::api::{Brush, Coords, Orientation, PlayerInfo, RequestParams};
+use crate::error::RequestError;
+use crate::render::{hand_pos, Outline, RenderFrame};
+use crate::theme::Theme;
+use crate::variant::Variant;
+
+const FONT_FAMILY: &str = "Noto Sans, sans-serif";
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn piece_id(piece: Piece, orientation: Orientation) -> String {
+    let name = match piece.piece_type {
+        PieceType::King if piece.color == Color::Black => "tama",
+        PieceType::King => "king",
+        PieceType::Rook => "rook",
+        PieceType::Bishop => "bishop",
+        PieceType::Gold => "gold",
+        PieceType::Silver => "silver",
+        PieceType::Knight => "knight",
+        PieceType::Lance => "lance",
+        PieceType::Pawn => "pawn",
+        PieceType::ProRook => "dragon",
+        PieceType::ProBishop => "horse",
+        PieceType::ProSilver => "prosilver",
+        PieceType::ProKnight => "proknight",
+        PieceType::ProLance => "prolance",
+        PieceType::ProPawn => "tokin",
+    };
+    // Upright pieces are drawn as black in the sprite.
+    let color = if orientation.eq_color(piece.color) {
+        "black"
+    } else {
+        "white"
+    };
+    format!("#{}-{}", color, name)
+}
+
+/// Renders a single position as a standalone SVG document.
+pub fn render(theme: &Theme, params: RequestParams) -> Result<String, RequestError> {
+    let frame = RenderFrame::new(
+        params.variant,
+        params.sfen.as_deref(),
+        params.last_move.as_deref(),
+        params.check,
+        params.shapes,
+        None,
+    )?;
+    let variant = params.variant;
+    let coords = params.coords;
+    let orientation = params.orientation;
+
+    let bars = params.black.is_some() || params.white.is_some();
+    let top = theme.board_top(bars, coords);
+    let width = theme.width(variant, coords);
+    let height = theme.height(variant, bars, coords, false);
+    let comment = params
+        .comment
+        .as_ref()
+        .map_or("https://github.com/WandererXII/lishogi-gif", |c| c.as_str());
+
+    let mut svg = String::new();
+    write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" version="1.1" xmlns:xlink="http://www.w3.org/1999/xlink" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = width,
+        h = height
+    )
+    .expect("write svg");
+    if !comment.is_empty() {
+        write!(svg, "<desc>{}</desc>", escape(comment)).expect("write desc");
+    }
+    svg.push_str(theme.defs());
+
+    if bars {
+        let black = (params.black.unwrap_or_default(), params.players.info(Color::Black));
+        let white = (params.white.unwrap_or_default(), params.players.info(Color::White));
+        let (upper, lower) = orientation.fold((&white, &black), (&black, &white));
+        render_bar(
+            &mut svg,
+            theme,
+            width,
+            0,
+            params.transparent,
+            (upper.0.as_str(), upper.1),
+        );
+        render_bar(
+            &mut svg,
+            theme,
+            width,
+            top + theme.board_height(variant),
+            params.transparent,
+            (lower.0.as_str(), lower.1),
+        );
+    }
+
+    if coords != Coords::None {
+        render_coords(&mut svg, theme, variant, coords, orientation, top, params.transparent);
+    }
+
+    render_hand(&mut svg, theme, variant, top, 0, theme.hand_width() - 1);
+    render_hand(
+        &mut svg,
+        theme,
+        variant,
+        top,
+        width - theme.hand_width(),
+        width - theme.hand_width(),
+    );
+
+    for sq in variant.squares() {
+        let x = theme.hand_width() + orientation.x(variant, sq) * theme.square_width();
+        let y = top + orientation.y(variant, sq) * theme.square_height();
+        let fill = if frame.highlighted.is_occupied(sq) {
+            theme.rgb(theme.highlight_color())
+        } else {
+            theme.rgb(theme.square_color())
+        };
+        write!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" stroke="{}" stroke-width="{}" fill="{}"/>"#,
+            x,
+            y,
+            theme.square_width(),
+            theme.square_height(),
+            theme.rgb(theme.circle_color()),
+            theme.px(2.0),
+            fill
+        )
+        .expect("write square");
+        if frame.checked.is_occupied(sq) {
+            write!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="url(#check_gradient)"/>"#,
+                x,
+                y,
+                theme.square_width(),
+                theme.square_height()
+            )
+            .expect("write check");
+        }
+        if let Some(piece) = *frame.sfen.piece_at(sq) {
+            render_piece(&mut svg, theme, x, y, &piece_id(variant.face(piece), orientation), None);
+        }
+    }
+
+    for (file, rank) in variant.star_points() {
+        write!(
+            svg,
+            r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+            theme.hand_width() + file * theme.square_width(),
+            top + rank * theme.square_height(),
+            theme.circle(),
+            theme.rgb(theme.circle_color())
+        )
+        .expect("write circle");
+    }
+
+    for piece in variant.hand_pieces() {
+        let (left, y) = hand_pos(theme, variant, coords, orientation, piece);
+        render_hand_piece(
+            &mut svg,
+            theme,
+            orientation,
+            (left, top + y),
+            piece,
+            frame.sfen.hand(piece),
+        );
+    }
+
+    for shape in &frame.shapes {
+        render_shape(
+            &mut svg,
+            theme,
+            top,
+            &Outline::new(shape, theme, variant, coords, orientation),
+        );
+    }
+
+    svg.push_str("</svg>");
+    Ok(svg)
+}
+
+fn render_shape(svg: &mut String, theme: &Theme, top: usize, outline: &Outline) {
+    let color = theme.rgb(theme.brush_color(outline.brush()));
+    let top = top as f32;
+    match *outline {
+        Outline::Arrow { from, to, .. } => {
+            let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+            let len = dx.hypot(dy);
+            let (ux, uy) = (dx / len, dy / len);
+            let head = theme.arrow_head_length().min(len);
+            let (bx, by) = (to.0 - ux * head, to.1 - uy * head);
+            let (nx, ny) = (
+                -uy * theme.arrow_head_width() / 2.0,
+                ux * theme.arrow_head_width() / 2.0,
+            );
+            write!(
+                svg,
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}"/>"#,
+                from.0,
+                top + from.1,
+                bx,
+                top + by,
+                color,
+                theme.arrow_width()
+            )
+            .expect("write arrow");
+            write!(
+                svg,
+                r#"<polygon points="{},{} {},{} {},{}" fill="{}"/>"#,
+                to.0,
+                top + to.1,
+                bx + nx,
+                top + by + ny,
+                bx - nx,
+                top + by - ny,
+                color
+            )
+            .expect("write arrow head");
+        }
+        Outline::Circle { center, .. } => write!(
+            svg,
+            r#"<circle cx="{}" cy="{}" r="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+            center.0,
+            top + center.1,
+            theme.shape_circle_radius(),
+            color,
+            theme.shape_circle_width()
+        )
+        .expect("write circle"),
+    }
+}
+
+fn render_piece(svg: &mut String, theme: &Theme, x: usize, y: usize, id: &str, opacity: Option<f32>) {
+    write!(
+        svg,
+        r#"<svg x="{}" y="{}" width="{}" height="{}" viewBox="5 5 90 90"><use"#,
+        x,
+        y,
+        theme.square_width(),
+        theme.square_height()
+    )
+    .expect("write piece");
+    if let Some(opacity) = opacity {
+        write!(svg, r#" opacity="{}""#, opacity).expect("write opacity");
+    }
+    write!(svg, r#" xlink:href="{}"/></svg>"#, id).expect("write piece");
+}
+
+fn render_hand(svg: &mut String, theme: &Theme, variant: Variant, top: usize, left: usize, border: usize) {
+    write!(
+        svg,
+        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/><rect x="{}" y="{}" width="1" height="{}" fill="{}"/>"#,
+        left,
+        top,
+        theme.hand_width(),
+        theme.board_height(variant),
+        theme.rgb(theme.hand_color()),
+        border,
+        top,
+        theme.board_height(variant),
+        theme.rgb(theme.circle_color())
+    )
+    .expect("write hand");
+}
+
+fn render_hand_piece(
+    svg: &mut String,
+    theme: &Theme,
+    orientation: Orientation,
+    (left, top): (usize, usize),
+    piece: Piece,
+    nb: u8,
+) {
+    let nb = std::cmp::min(nb, 99);
+
+    let opacity = if nb > 0 { None } else { Some(0.1) };
+    render_piece(svg, theme, left, top, &piece_id(piece, orientation), opacity);
+
+    if nb > 0 {
+        let size = theme.square_width() as f32 * 2.0 / 5.0;
+        write!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" stroke="{}" stroke-width="{}" rx="{}" fill="{}"/>"#,
+            left as f32 + theme.square_width() as f32 * 3.0 / 5.0,
+            top as f32 + theme.square_height() as f32 * 3.0 / 5.0,
+            size,
+            size,
+            theme.rgb(theme.white_color()),
+            theme.px(2.0),
+            theme.px(8.0),
+            theme.rgb(theme.bar_color())
+        )
+        .expect("write counter");
+
+        // Same placement as the rasterized counter.
+        let font_size = theme.px(28.0 + (if nb < 10 { 2.0 } else { 0.0 }));
+        let x_offset = theme.px(56.0);
+        let y_offset = theme.px(50.0 + (if nb > 10 { 2.0 } else { 0.0 }));
+        let text = nb.to_string();
+        let center = (text.len() - 1) as f32 * theme.px(6.0);
+        let ascent = theme.font().v_metrics(Scale::uniform(font_size)).ascent;
+        write!(
+            svg,
+            r#"<text x="{}" y="{}" font-family="{}" font-size="{}" fill="{}">{}</text>"#,
+            x_offset + left as f32 - center,
+            y_offset + top as f32 + ascent,
+            FONT_FAMILY,
+            font_size,
+            theme.rgb(theme.white_color()),
+            text
+        )
+        .expect("write counter text");
+    }
+}
+
+/// File labels above the board and rank labels to its right, in the margin
+/// ending at `top`.
+fn render_coords(
+    svg: &mut String,
+    theme: &Theme,
+    variant: Variant,
+    coords: Coords,
+    orientation: Orientation,
+    top: usize,
+    transparent: bool,
+) {
+    let margin = theme.coords_margin(coords);
+    let board_right = theme.hand_width() + theme.board_width(variant);
+    if !transparent {
+        write!(
+            svg,
+            r#"<rect x="0" y="{}" width="{}" height="{}" fill="{bg}"/><rect x="{}" y="{}" width="{}" height="{}" fill="{bg}"/>"#,
+            top - margin,
+            theme.width(variant, coords),
+            margin,
+            board_right,
+            top,
+            margin,
+            theme.board_height(variant),
+            bg = theme.rgb(theme.bar_color())
+        )
+        .expect("write coords background");
+    }
+
+    let label = |svg: &mut String, x: usize, y: usize, text: char| {
+        write!(
+            svg,
+            r#"<text x="{}" y="{}" font-family="{}" font-size="{}" fill="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+            x,
+            y,
+            FONT_FAMILY,
+            theme.px(24.0),
+            theme.rgb(theme.text_color()),
+            text
+        )
+        .expect("write coord");
+    };
+    for sq in variant.squares() {
+        if sq.rank() == 0 {
+            let x = theme.hand_width() + orientation.x(variant, sq) * theme.square_width() + theme.square_width() / 2;
+            label(svg, x, top - margin / 2, coords.file_label(sq.file()));
+        }
+        if sq.file() == 0 {
+            let y = top + orientation.y(variant, sq) * theme.square_height() + theme.square_height() / 2;
+            label(svg, board_right + margin / 2, y, coords.rank_label(sq.rank()));
+        }
+    }
+}
+
+fn render_bar(
+    svg: &mut String,
+    theme: &Theme,
+    width: usize,
+    top: usize,
+    transparent: bool,
+    (player_name, info): (&str, PlayerInfo),
+) {
+    if !transparent {
+        write!(
+            svg,
+            r#"<rect x="0" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            top,
+            width,
+            theme.bar_height(),
+            theme.rgb(theme.bar_color())
+        )
+        .expect("write bar");
+    }
+
+    let gap = theme.px(10.0);
+    let center = top as f32 + theme.bar_height() as f32 / 2.0;
+    let mut x = gap + theme.hand_width() as f32;
+
+    if let Some(online) = info.online {
+        let radius = theme.px(7.0);
+        let color = if online {
+            theme.brush_color(Brush::Green)
+        } else {
+            theme.med_text_color()
+        };
+        write!(
+            svg,
+            r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+            x + radius,
+            center,
+            radius,
+            theme.rgb(color)
+        )
+        .expect("write online marker");
+        x += 2.0 * radius + gap;
+    }
+
+    if info.patron {
+        let radius = theme.px(9.0);
+        write!(
+            svg,
+            r#"<polygon points="{l},{c} {m},{t} {r},{c} {m},{b}" fill="{}"/>"#,
+            theme.rgb(theme.gold_color()),
+            l = x,
+            m = x + radius,
+            r = x + 2.0 * radius,
+            t = center - radius,
+            c = center,
+            b = center + radius
+        )
+        .expect("write patron marker");
+        x += 2.0 * radius + gap;
+    }
+
+    let height = theme.px(40.0);
+    let padding = theme.px(10.0);
+    let ascent = theme.font().v_metrics(Scale::uniform(height)).ascent;
+    write!(
+        svg,
+        r#"<text x="{}" y="{}" font-family="{}" font-size="{}" fill="{}" xml:space="preserve">"#,
+        x,
+        top as f32 + padding + ascent,
+        FONT_FAMILY,
+        height,
+        theme.rgb(theme.text_color())
+    )
+    .expect("write bar text");
+    if let Some(title) = info.title {
+        let color = if info.is_bot() {
+            theme.bot_color()
+        } else {
+            theme.gold_color()
+        };
+        write!(svg, r#"<tspan fill="{}">{}</tspan> "#, theme.rgb(color), escape(&title)).expect("write title");
+    }
+    svg.push_str(&escape(player_name));
+    if let Some(rating) = info.rating {
+        write!(
+            svg,
+            r#" <tspan fill="{}">{}</tspan>"#,
+            theme.rgb(theme.med_text_color()),
+            rating
+        )
+        .expect("write rating");
+    }
+    if let Some(diff) = info.rating_diff {
+        let color = match diff {
+            d if d > 0 => theme.brush_color(Brush::Green),
+            d if d < 0 => theme.brush_color(Brush::Red),
+            _ => theme.med_text_color(),
+        };
+        write!(svg, r#" <tspan fill="{}">{:+}</tspan>"#, theme.rgb(color), diff).expect("write rating diff");
+    }
+    if let Some(flag) = info.flag {
+        write!(
+            svg,
+            r#" <tspan fill="{}">{}</tspan>"#,
+            theme.rgb(theme.med_text_color()),
+            escape(&flag)
+        )
+        .expect("write flag");
+    }
+    svg.push_str("</text>");
+}
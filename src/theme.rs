@@ -1,14 +1,73 @@
 use gift::block::{ColorTableConfig, GlobalColorTable};
 use ndarray::{s, Array2, ArrayView2};
 use rusttype::Font;
+use serde::Deserialize;
 use shogi::{Color, Piece, PieceType};
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+use crate::api::{Brush, Coords, Orientation};
+use crate::error::RequestError;
+use crate::variant::Variant;
+
+/// Largest scale offered, and the scale at which fonts and offsets are
+/// designed. Smaller scales are generated at startup.
+pub const SCALE: usize = 7;
+pub const MIN_SCALE: usize = 2;
+const CIRCLE: usize = 5;
 
-use crate::api::Orientation;
+// Sprite grid addressed by `SpriteKey` and `SpriteHandKey`.
+const COLUMNS: usize = 12;
+const ROWS: usize = 9;
 
-const SCALE: usize = 7;
-const SQUARE_WIDTH: usize = 11 * SCALE;
-const SQUARE_HEIGHT: usize = 12 * SCALE;
-const CIRCLE: usize = 5;
+pub const DEFAULT_BOARD: &str = "orange";
+pub const DEFAULT_PIECE_SET: &str = "ryoko_1kanji";
+
+macro_rules! sprite {
+    ($board:literal, $piece_set:literal) => {
+        (
+            $board,
+            $piece_set,
+            include_bytes!(concat!("../theme/sprite-", $board, "-", $piece_set, ".gif")),
+            include_str!(concat!("../theme/sprite-", $board, "-", $piece_set, ".svg")),
+        )
+    };
+}
+
+/// Sprite sheets and the SVG they were rendered from, generated by
+/// `make-sprite.py` for each board and piece set.
+const SPRITES: &[(&str, &str, &[u8], &str)] = &[
+    (
+        DEFAULT_BOARD,
+        DEFAULT_PIECE_SET,
+        include_bytes!("../theme/sprite.gif"),
+        include_str!("../theme/sprite.svg"),
+    ),
+    sprite!("orange", "western"),
+    sprite!("orange", "international"),
+    sprite!("wood", "ryoko_1kanji"),
+    sprite!("wood", "western"),
+    sprite!("wood", "international"),
+    sprite!("blue", "ryoko_1kanji"),
+    sprite!("blue", "western"),
+    sprite!("blue", "international"),
+    sprite!("gray", "ryoko_1kanji"),
+    sprite!("gray", "western"),
+    sprite!("gray", "international"),
+];
+
+const FONT: &[u8] = include_bytes!("../theme/NotoSans-Regular.ttf");
+
+// Brush colors of lishogi shapes, in the order of `Brush`.
+const BRUSH_COLORS: [[u8; 3]; 4] = [
+    [0x15, 0x78, 0x1b],
+    [0x88, 0x20, 0x20],
+    [0x00, 0x30, 0x88],
+    [0xe6, 0x8f, 0x00],
+];
 
 pub struct SpriteHandKey {
     pub piece: Piece,
@@ -32,14 +91,20 @@ impl SpriteHandKey {
 
 pub struct SpriteKey {
     pub piece: Option<Piece>,
+    pub variant: Variant,
     pub orientation: Orientation,
     pub highlight: bool,
     pub check: bool,
 }
 
 impl SpriteKey {
+    /// The piece as drawn, which differs from the piece for some variants.
+    fn face(&self) -> Option<Piece> {
+        self.piece.map(|piece| self.variant.face(piece))
+    }
+
     fn x(&self) -> usize {
-        let mx = match self.piece {
+        let mx = match self.face() {
             Some(piece) if self.check && piece.piece_type == PieceType::King => 3,
             Some(piece) if piece.piece_type == PieceType::King && piece.color == Color::Black => 4,
             Some(piece) => (piece.piece_type as usize) / 4,
@@ -49,7 +114,7 @@ impl SpriteKey {
     }
 
     fn y(&self) -> usize {
-        match self.piece {
+        match self.face() {
             Some(piece) if self.check && piece.piece_type == PieceType::King && piece.color == Color::Black => {
                 5 + self.orientation.fold(1, 0)
             }
@@ -68,39 +133,184 @@ impl SpriteKey {
 }
 
 pub struct Theme {
+    board: String,
+    piece_set: String,
+    scale: usize,
     color_table_config: ColorTableConfig,
     global_color_table: GlobalColorTable,
+    palette: Vec<u8>,
+    brush_base: u8,
     sprite: Array2<u8>,
+    defs: String,
     font: Font<'static>,
 }
 
 impl Theme {
-    pub fn new() -> Theme {
-        let sprite_data = include_bytes!("../theme/sprite.gif") as &[u8];
-        let mut decoder = gift::Decoder::new(std::io::Cursor::new(sprite_data)).into_frames();
-        let preamble = decoder.preamble().expect("decode preamble").expect("preamble");
-        let frame = decoder.next().expect("frame").expect("decode frame");
-        let sprite = Array2::from_shape_vec(
-            (SQUARE_HEIGHT * 9, SQUARE_WIDTH * 12),
-            frame.image_data.data().to_owned(),
-        )
-        .expect("from shape");
+    pub fn new(
+        board: &str,
+        piece_set: &str,
+        scale: usize,
+        sprite_data: &[u8],
+        svg: &str,
+        font: Font<'static>,
+    ) -> Result<Theme, ThemeError> {
+        let invalid = |reason: &str| ThemeError::InvalidSprite {
+            board: board.to_owned(),
+            piece_set: piece_set.to_owned(),
+            reason: reason.to_owned(),
+        };
 
-        let font_data = include_bytes!("../theme/NotoSans-Regular.ttf") as &[u8];
-        let font = Font::try_from_bytes(font_data).expect("parse font");
+        // Shapes are not part of the sprite, so the global color table is
+        // doubled to make room for the brush colors. It directly follows the
+        // 13 byte header and logical screen descriptor.
+        if sprite_data.len() < 13 || sprite_data[10] & 0x80 == 0 || sprite_data[10] & 0x07 > 6 {
+            return Err(invalid("expected a global color table with at most 128 colors"));
+        }
+        let mut sprite_data = sprite_data.to_vec();
+        let palette_len = 3 * (2 << (sprite_data[10] & 0x07));
+        if sprite_data.len() < 13 + palette_len {
+            return Err(invalid("truncated global color table"));
+        }
+        let brush_base = (palette_len / 3) as u8;
+        let mut brushes = vec![0; palette_len];
+        for (i, color) in BRUSH_COLORS.iter().enumerate() {
+            brushes[(3 * i)..(3 * i + 3)].copy_from_slice(color);
+        }
+        sprite_data.splice((13 + palette_len)..(13 + palette_len), brushes);
+        sprite_data[10] += 1;
+        let palette = sprite_data[13..(13 + 2 * palette_len)].to_vec();
 
-        Theme {
+        let mut decoder = gift::Decoder::new(std::io::Cursor::new(sprite_data)).into_frames();
+        let preamble = decoder
+            .preamble()
+            .map_err(|_| invalid("could not decode gif"))?
+            .ok_or_else(|| invalid("missing gif preamble"))?;
+        let (height, width) = (12 * scale * ROWS, 11 * scale * COLUMNS);
+        let screen = &preamble.logical_screen_desc;
+        if usize::from(screen.screen_width()) != width || usize::from(screen.screen_height()) != height {
+            return Err(invalid("sheet dimensions do not match the sprite grid"));
+        }
+        let frame = decoder
+            .next()
+            .ok_or_else(|| invalid("missing gif frame"))?
+            .map_err(|_| invalid("could not decode gif frame"))?;
+        let sprite = Array2::from_shape_vec((height, width), frame.image_data.data().to_owned())
+            .map_err(|_| invalid("image does not cover the sheet"))?;
+
+        // Piece artwork and gradients, for vector output.
+        let defs = match (svg.find("<defs>"), svg.find("</defs>")) {
+            (Some(start), Some(end)) if start < end => svg[start..(end + "</defs>".len())].to_owned(),
+            _ => return Err(invalid("missing <defs> in svg")),
+        };
+
+        Ok(Theme {
+            board: board.to_owned(),
+            piece_set: piece_set.to_owned(),
+            scale,
             color_table_config: preamble.logical_screen_desc.color_table_config(),
-            global_color_table: preamble.global_color_table.expect("color table present"),
+            global_color_table: preamble
+                .global_color_table
+                .ok_or_else(|| invalid("missing global color table"))?,
+            palette,
+            brush_base,
             sprite,
+            defs,
             font,
+        })
+    }
+
+    /// Derives a smaller theme by area averaging the sprite. Pixels that
+    /// mix colors are mapped to the nearest opaque color of the palette.
+    fn with_scale(&self, scale: usize) -> Theme {
+        let weights = |len: usize| -> Vec<Vec<(usize, usize)>> {
+            (0..len)
+                .map(|d| {
+                    let (start, end) = (d * self.scale, (d + 1) * self.scale);
+                    ((start / scale)..end.div_ceil(scale))
+                        .map(|i| (i, std::cmp::min(end, (i + 1) * scale) - std::cmp::max(start, i * scale)))
+                        .collect()
+                })
+                .collect()
+        };
+
+        let (height, width) = (12 * scale * ROWS, 11 * scale * COLUMNS);
+        let rows = weights(height);
+        let cols = weights(width);
+        let transparent = self.transparent_color();
+        let opaque: Vec<u8> = (0..self.brush_base).filter(|idx| *idx != transparent).collect();
+
+        let sprite = Array2::from_shape_fn((height, width), |(y, x)| {
+            let first = self.sprite[(rows[y][0].0, cols[x][0].0)];
+            let mut uniform = true;
+            let mut rgb = [0; 3];
+            for &(sy, wy) in &rows[y] {
+                for &(sx, wx) in &cols[x] {
+                    let idx = self.sprite[(sy, sx)];
+                    uniform &= idx == first;
+                    for (c, sum) in rgb.iter_mut().enumerate() {
+                        *sum += wy * wx * usize::from(self.palette[3 * usize::from(idx) + c]);
+                    }
+                }
+            }
+            if uniform {
+                return first;
+            }
+
+            let rgb = rgb.map(|sum| (sum / (self.scale * self.scale)) as i32);
+            *opaque
+                .iter()
+                .min_by_key(|idx| {
+                    let color = &self.palette[(3 * usize::from(**idx))..(3 * usize::from(**idx) + 3)];
+                    (0..3).map(|c| (rgb[c] - i32::from(color[c])).pow(2)).sum::<i32>()
+                })
+                .expect("opaque colors")
+        });
+
+        Theme {
+            board: self.board.clone(),
+            piece_set: self.piece_set.clone(),
+            scale,
+            color_table_config: self.color_table_config,
+            global_color_table: self.global_color_table.clone(),
+            palette: self.palette.clone(),
+            brush_base: self.brush_base,
+            sprite,
+            defs: self.defs.clone(),
+            font: self.font.clone(),
         }
     }
 
+    pub fn board(&self) -> &str {
+        &self.board
+    }
+
+    pub fn piece_set(&self) -> &str {
+        &self.piece_set
+    }
+
+    pub fn scale(&self) -> usize {
+        self.scale
+    }
+
+    /// The `<defs>` element with the artwork of the piece set.
+    pub fn defs(&self) -> &str {
+        &self.defs
+    }
+
+    /// Converts a length at the scale of the prerendered sprite to the
+    /// scale of this theme.
+    pub fn px(&self, length: f32) -> f32 {
+        length * self.scale as f32 / SCALE as f32
+    }
+
     pub fn font(&self) -> &Font {
         &self.font
     }
 
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.font.glyph(c).id().0 != 0
+    }
+
     pub fn color_table_config(&self) -> ColorTableConfig {
         self.color_table_config
     }
@@ -109,52 +319,109 @@ impl Theme {
         &self.global_color_table
     }
 
+    /// RGB triples of the global color table.
+    pub fn palette(&self) -> &[u8] {
+        &self.palette
+    }
+
     pub fn bar_color(&self) -> u8 {
         self.sprite[(0, 0)]
     }
 
     pub fn text_color(&self) -> u8 {
-        self.sprite[(0, SQUARE_WIDTH)]
+        self.sprite[(0, self.square_width())]
     }
 
     pub fn gold_color(&self) -> u8 {
-        self.sprite[(0, SQUARE_WIDTH * 2)]
+        self.sprite[(0, self.square_width() * 2)]
     }
 
     pub fn bot_color(&self) -> u8 {
-        self.sprite[(0, SQUARE_WIDTH * 3)]
+        self.sprite[(0, self.square_width() * 3)]
     }
 
     pub fn med_text_color(&self) -> u8 {
-        self.sprite[(0, SQUARE_WIDTH * 4)]
+        self.sprite[(0, self.square_width() * 4)]
     }
 
     pub fn hand_color(&self) -> u8 {
-        self.sprite[(0, SQUARE_WIDTH * 5)]
+        self.sprite[(0, self.square_width() * 5)]
     }
 
     pub fn white_color(&self) -> u8 {
-        self.sprite[(0, SQUARE_WIDTH * 6)]
+        self.sprite[(0, self.square_width() * 6)]
     }
 
     pub fn transparent_color(&self) -> u8 {
-        self.sprite[(0, SQUARE_WIDTH * 7)]
+        self.sprite[(0, self.square_width() * 7)]
     }
 
     pub fn circle_color(&self) -> u8 {
-        self.sprite[(SQUARE_HEIGHT + SQUARE_HEIGHT / 2, SQUARE_WIDTH * 10 + SQUARE_WIDTH / 2)]
+        self.sprite[(
+            self.square_height() + self.square_height() / 2,
+            self.square_width() * 10 + self.square_width() / 2,
+        )]
+    }
+
+    pub fn square_color(&self) -> u8 {
+        self.sprite[(
+            self.square_height() / 2,
+            self.square_width() * 10 + self.square_width() / 2,
+        )]
+    }
+
+    pub fn highlight_color(&self) -> u8 {
+        self.sprite[(
+            self.square_height() / 2,
+            self.square_width() * 11 + self.square_width() / 2,
+        )]
+    }
+
+    /// RGB hex color of a palette entry, for vector output.
+    pub fn rgb(&self, color: u8) -> String {
+        let idx = 3 * usize::from(color);
+        format!(
+            "#{:02x}{:02x}{:02x}",
+            self.palette[idx],
+            self.palette[idx + 1],
+            self.palette[idx + 2]
+        )
+    }
+
+    pub fn brush_color(&self, brush: Brush) -> u8 {
+        self.brush_base + brush as u8
     }
 
     pub fn square_width(&self) -> usize {
-        SQUARE_WIDTH
+        11 * self.scale
     }
 
     pub fn square_height(&self) -> usize {
-        SQUARE_HEIGHT
+        12 * self.scale
     }
 
     pub fn circle(&self) -> usize {
-        CIRCLE
+        std::cmp::max(1, (CIRCLE * self.scale + SCALE / 2) / SCALE)
+    }
+
+    pub fn arrow_width(&self) -> f32 {
+        self.square_width() as f32 * 10.0 / 64.0
+    }
+
+    pub fn arrow_head_length(&self) -> f32 {
+        self.arrow_width() * 2.4
+    }
+
+    pub fn arrow_head_width(&self) -> f32 {
+        self.arrow_width() * 3.2
+    }
+
+    pub fn shape_circle_width(&self) -> f32 {
+        self.square_width() as f32 * 4.0 / 64.0
+    }
+
+    pub fn shape_circle_radius(&self) -> f32 {
+        self.square_width() as f32 / 2.0 - self.shape_circle_width()
     }
 
     pub fn hand_width(&self) -> usize {
@@ -165,33 +432,68 @@ impl Theme {
         self.square_width() / 3
     }
 
-    pub fn board_width(&self) -> usize {
-        self.square_width() * 9
+    pub fn board_width(&self, variant: Variant) -> usize {
+        self.square_width() * variant.size()
     }
 
-    pub fn board_height(&self) -> usize {
-        self.square_height() * 9
+    pub fn board_height(&self, variant: Variant) -> usize {
+        self.square_height() * variant.size()
     }
 
-    pub fn width(&self) -> usize {
-        self.square_width() * 12
+    pub fn width(&self, variant: Variant, coords: Coords) -> usize {
+        self.square_width() * (variant.size() + 3) + self.coords_margin(coords)
     }
 
-    pub fn bar_height(&self) -> usize {
-        60
+    /// Space for the file labels above the board and the rank labels to
+    /// its right.
+    pub fn coords_margin(&self, coords: Coords) -> usize {
+        match coords {
+            Coords::None => 0,
+            Coords::Western | Coords::Japanese => 30 * self.scale / SCALE,
+        }
     }
 
-    pub fn height(&self, bars: bool) -> usize {
+    pub fn board_top(&self, bars: bool, coords: Coords) -> usize {
         if bars {
-            self.square_height() * 9 + 2 * self.bar_height()
+            self.bar_height() + self.coords_margin(coords)
         } else {
-            self.square_height() * 9
+            self.coords_margin(coords)
+        }
+    }
+
+    pub fn bar_height(&self) -> usize {
+        60 * self.scale / SCALE
+    }
+
+    pub fn graph_height(&self) -> usize {
+        120 * self.scale / SCALE
+    }
+
+    pub fn eval_width(&self) -> usize {
+        20 * self.scale / SCALE
+    }
+
+    pub fn clock_width(&self) -> usize {
+        180 * self.scale / SCALE
+    }
+
+    /// Height of the image, with or without player bars and a caption strip
+    /// below everything else.
+    pub fn height(&self, variant: Variant, bars: bool, coords: Coords, caption: bool) -> usize {
+        let mut height = self.board_height(variant) + self.coords_margin(coords);
+        if bars {
+            height += 2 * self.bar_height();
         }
+        if caption {
+            height += self.bar_height();
+        }
+        height
     }
 
     pub fn circle_sprite(&self, bottom: bool, right: bool) -> ArrayView2<u8> {
-        let circle_center_top = SQUARE_HEIGHT + SQUARE_HEIGHT / 2 - if bottom { self.circle() } else { 0 };
-        let circle_center_left = SQUARE_WIDTH * 10 + SQUARE_WIDTH / 2 - if right { self.circle() } else { 0 };
+        let (square_width, square_height) = (self.square_width(), self.square_height());
+        let circle_center_top = square_height + square_height / 2 - if bottom { self.circle() } else { 0 };
+        let circle_center_left = square_width * 10 + square_width / 2 - if right { self.circle() } else { 0 };
         self.sprite.slice(s!(
             (circle_center_top)..(circle_center_top + self.circle()),
             (circle_center_left)..(circle_center_left + self.circle())
@@ -201,18 +503,214 @@ impl Theme {
     pub fn sprite(&self, key: SpriteKey) -> ArrayView2<u8> {
         let y = key.y() % 9;
         let x = key.x() % 12;
+        let (square_width, square_height) = (self.square_width(), self.square_height());
         self.sprite.slice(s!(
-            (SQUARE_HEIGHT * y)..(SQUARE_HEIGHT + SQUARE_HEIGHT * y),
-            (SQUARE_WIDTH * x)..(SQUARE_WIDTH + SQUARE_WIDTH * x)
+            (square_height * y)..(square_height + square_height * y),
+            (square_width * x)..(square_width + square_width * x)
         ))
     }
 
     pub fn hand_sprite(&self, key: SpriteHandKey) -> ArrayView2<u8> {
         let y = key.y() % 9;
         let x = key.x() % 12;
+        let (square_width, square_height) = (self.square_width(), self.square_height());
         self.sprite.slice(s!(
-            (SQUARE_HEIGHT * y + 1)..(SQUARE_HEIGHT + SQUARE_HEIGHT * y),
-            (SQUARE_WIDTH * x + 1)..(SQUARE_WIDTH + SQUARE_WIDTH * x)
+            (square_height * y + 1)..(square_height + square_height * y),
+            (square_width * x + 1)..(square_width + square_width * x)
         ))
     }
 }
+
+#[derive(Debug)]
+pub enum ThemeError {
+    Io(PathBuf, io::Error),
+    Manifest(serde_json::Error),
+    InvalidLayout(&'static str),
+    InvalidFont(PathBuf),
+    MissingDefault,
+    InvalidSprite {
+        board: String,
+        piece_set: String,
+        reason: String,
+    },
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThemeError::Io(path, err) => write!(f, "could not read {}: {}", path.display(), err),
+            ThemeError::Manifest(err) => write!(f, "invalid theme manifest: {}", err),
+            ThemeError::InvalidLayout(reason) => write!(f, "invalid sprite layout: {}", reason),
+            ThemeError::InvalidFont(path) => write!(f, "invalid font {}", path.display()),
+            ThemeError::MissingDefault => write!(
+                f,
+                "theme manifest has no sprite for {} with {}",
+                DEFAULT_BOARD, DEFAULT_PIECE_SET
+            ),
+            ThemeError::InvalidSprite {
+                board,
+                piece_set,
+                reason,
+            } => write!(f, "invalid sprite for {} with {}: {}", board, piece_set, reason),
+        }
+    }
+}
+
+/// Contents of `theme.json` in the theme directory.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+    font: PathBuf,
+    square_width: usize,
+    square_height: usize,
+    columns: usize,
+    rows: usize,
+    sprites: Vec<ManifestSprite>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ManifestSprite {
+    board: String,
+    piece_set: String,
+    gif: PathBuf,
+    svg: PathBuf,
+}
+
+impl Manifest {
+    /// Validates the layout against the grid of `SpriteKey` and
+    /// `SpriteHandKey`, returning the scale of the sprites.
+    fn scale(&self) -> Result<usize, ThemeError> {
+        if self.columns != COLUMNS || self.rows != ROWS {
+            return Err(ThemeError::InvalidLayout("expected 12 columns and 9 rows"));
+        }
+        if !self.square_width.is_multiple_of(11) || self.square_height * 11 != self.square_width * 12 {
+            return Err(ThemeError::InvalidLayout("squares must be 11 by 12 units"));
+        }
+        let scale = self.square_width / 11;
+        if scale < SCALE {
+            return Err(ThemeError::InvalidLayout("squares must be at least 77 by 84 pixels"));
+        }
+        Ok(scale)
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, ThemeError> {
+    fs::read(path).map_err(|err| ThemeError::Io(path.to_owned(), err))
+}
+
+/// Themes for every board, piece set and supported scale.
+pub struct Themes {
+    themes: Vec<Arc<Theme>>,
+}
+
+impl Themes {
+    /// Themes embedded in the binary.
+    pub fn new() -> Themes {
+        let font = Font::try_from_bytes(FONT).expect("parse font");
+        let mut themes = Themes { themes: Vec::new() };
+        for (board, piece_set, sprite, svg) in SPRITES {
+            themes.push(Theme::new(board, piece_set, SCALE, sprite, svg, font.clone()).expect("embedded theme"));
+        }
+        themes
+    }
+
+    /// Loads themes as described by `theme.json` in the directory.
+    pub fn load(dir: &Path) -> Result<Themes, ThemeError> {
+        let manifest: Manifest =
+            serde_json::from_slice(&read(&dir.join("theme.json"))?).map_err(ThemeError::Manifest)?;
+        let scale = manifest.scale()?;
+        // Requests without board or piece set fall back to the default, so
+        // it must always be there.
+        if !manifest
+            .sprites
+            .iter()
+            .any(|sprite| sprite.board == DEFAULT_BOARD && sprite.piece_set == DEFAULT_PIECE_SET)
+        {
+            return Err(ThemeError::MissingDefault);
+        }
+
+        let font_path = dir.join(&manifest.font);
+        let font = Font::try_from_vec(read(&font_path)?).ok_or(ThemeError::InvalidFont(font_path))?;
+
+        let mut themes = Themes { themes: Vec::new() };
+        for sprite in &manifest.sprites {
+            let svg_path = dir.join(&sprite.svg);
+            let svg = String::from_utf8(read(&svg_path)?)
+                .map_err(|err| ThemeError::Io(svg_path, io::Error::new(io::ErrorKind::InvalidData, err)))?;
+            themes.push(Theme::new(
+                &sprite.board,
+                &sprite.piece_set,
+                scale,
+                &read(&dir.join(&sprite.gif))?,
+                &svg,
+                font.clone(),
+            )?);
+        }
+        Ok(themes)
+    }
+
+    fn push(&mut self, base: Theme) {
+        self.themes
+            .extend((MIN_SCALE..SCALE).map(|scale| Arc::new(base.with_scale(scale))));
+        if base.scale() == SCALE {
+            self.themes.push(Arc::new(base));
+        } else {
+            self.themes.push(Arc::new(base.with_scale(SCALE)));
+        }
+    }
+
+    pub fn get(
+        &self,
+        board: Option<&str>,
+        piece_set: Option<&str>,
+        scale: Option<u8>,
+    ) -> Result<Arc<Theme>, RequestError> {
+        let board = board.unwrap_or(DEFAULT_BOARD);
+        let piece_set = piece_set.unwrap_or(DEFAULT_PIECE_SET);
+        let scale = scale.map_or(SCALE, usize::from);
+        if !(MIN_SCALE..=SCALE).contains(&scale) {
+            return Err(RequestError::InvalidScale(scale));
+        }
+
+        self.themes
+            .iter()
+            .find(|theme| theme.board() == board && theme.piece_set() == piece_set && theme.scale() == scale)
+            .cloned()
+            .ok_or_else(|| RequestError::UnknownTheme(board.to_owned(), piece_set.to_owned()))
+    }
+}
+
+/// The current themes. Reloading swaps in new themes for new requests,
+/// while running renders keep the theme they started with.
+pub struct ThemeRegistry {
+    dir: Option<PathBuf>,
+    themes: RwLock<Arc<Themes>>,
+}
+
+impl ThemeRegistry {
+    pub fn new(dir: Option<PathBuf>) -> Result<ThemeRegistry, ThemeError> {
+        let themes = match dir {
+            Some(ref dir) => Themes::load(dir)?,
+            None => Themes::new(),
+        };
+        Ok(ThemeRegistry {
+            dir,
+            themes: RwLock::new(Arc::new(themes)),
+        })
+    }
+
+    pub fn current(&self) -> Arc<Themes> {
+        self.themes.read().expect("themes lock").clone()
+    }
+
+    /// Reloads themes from the theme directory. Embedded themes never
+    /// change, so there is nothing to do without one.
+    pub fn reload(&self) -> Result<(), ThemeError> {
+        if let Some(ref dir) = self.dir {
+            let themes = Themes::load(dir)?;
+            *self.themes.write().expect("themes lock") = Arc::new(themes);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,761 @@
+use serde::Deserialize;
+use shogi::{bitboard::Factory, Bitboard, Color, Move, Piece, PieceType, Position, Square};
+use std::{fmt, str::FromStr};
+
+use crate::api::STARTING_POSITION;
+use crate::error::RequestError;
+
+#[derive(Deserialize, PartialEq, Eq, Copy, Clone, Debug)]
+#[serde(try_from = "String")]
+pub enum Variant {
+    Standard,
+    Minishogi,
+    Kyotoshogi,
+}
+
+impl Default for Variant {
+    fn default() -> Variant {
+        Variant::Standard
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Variant::Standard => "standard",
+            Variant::Minishogi => "minishogi",
+            Variant::Kyotoshogi => "kyotoshogi",
+        })
+    }
+}
+
+impl FromStr for Variant {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Variant, String> {
+        match name {
+            "standard" => Ok(Variant::Standard),
+            "minishogi" => Ok(Variant::Minishogi),
+            "kyotoshogi" => Ok(Variant::Kyotoshogi),
+            // Positions are held in the 9x9 `Position` of the shogi crate,
+            // which has neither the 12x12 board nor the chushogi pieces.
+            "chushogi" => Err("chushogi is not supported".to_owned()),
+            _ => Err(format!("unknown variant {}", name)),
+        }
+    }
+}
+
+impl TryFrom<String> for Variant {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Variant, String> {
+        name.parse()
+    }
+}
+
+const STANDARD_HAND: &[PieceType] = &[
+    PieceType::Rook,
+    PieceType::Bishop,
+    PieceType::Gold,
+    PieceType::Silver,
+    PieceType::Knight,
+    PieceType::Lance,
+    PieceType::Pawn,
+];
+
+const MINISHOGI_HAND: &[PieceType] = &[
+    PieceType::Rook,
+    PieceType::Bishop,
+    PieceType::Gold,
+    PieceType::Silver,
+    PieceType::Pawn,
+];
+
+/// Kyoto shogi pieces are held with the face they show when unpromoted.
+const KYOTOSHOGI_HAND: &[PieceType] = &[PieceType::Silver, PieceType::Knight, PieceType::Lance, PieceType::Pawn];
+
+/// Small boards are kept in the top right corner of a standard position,
+/// so that they cover the squares 5a to 1e.
+const SMALL_SIZE: usize = 5;
+
+impl Variant {
+    /// Number of files, which is also the number of ranks.
+    pub fn size(self) -> usize {
+        match self {
+            Variant::Standard => 9,
+            Variant::Minishogi | Variant::Kyotoshogi => SMALL_SIZE,
+        }
+    }
+
+    pub fn starting_position(self) -> &'static str {
+        match self {
+            Variant::Standard => STARTING_POSITION,
+            Variant::Minishogi => "rbsgk/4p/5/P4/KGSBR b - 1",
+            Variant::Kyotoshogi => "p+nks+l/5/5/5/+LSK+NP b - 1",
+        }
+    }
+
+    pub fn contains(self, sq: Square) -> bool {
+        usize::from(sq.file()) < self.size() && usize::from(sq.rank()) < self.size()
+    }
+
+    pub fn squares(self) -> Bitboard {
+        Factory::all()
+            .filter(|sq| self.contains(*sq))
+            .fold(Bitboard::empty(), |bb, sq| bb | Bitboard::from_square(sq))
+    }
+
+    /// Piece types that can be held in hand, in SFEN order.
+    pub fn hand_piece_types(self) -> &'static [PieceType] {
+        match self {
+            Variant::Standard => STANDARD_HAND,
+            Variant::Minishogi => MINISHOGI_HAND,
+            Variant::Kyotoshogi => KYOTOSHOGI_HAND,
+        }
+    }
+
+    pub fn hand_pieces(self) -> Vec<Piece> {
+        Color::iter()
+            .flat_map(|color| {
+                self.hand_piece_types()
+                    .iter()
+                    .map(move |&piece_type| Piece { piece_type, color })
+            })
+            .collect()
+    }
+
+    /// Position of the piece type in the hand, starting at 1.
+    pub fn hand_index(self, piece_type: PieceType) -> usize {
+        1 + self
+            .hand_piece_types()
+            .iter()
+            .position(|&pt| pt == piece_type)
+            .expect("hand piece type")
+    }
+
+    /// Files and ranks of the star points, counted from the top left.
+    pub fn star_points(self) -> &'static [(usize, usize)] {
+        match self {
+            Variant::Standard => &[(3, 3), (6, 3), (3, 6), (6, 6)],
+            Variant::Minishogi | Variant::Kyotoshogi => &[],
+        }
+    }
+
+    /// The standard piece that looks and moves like the piece. Kyoto shogi
+    /// pieces promote to their other face, which is not the usual promoted
+    /// piece.
+    pub fn face(self, piece: Piece) -> Piece {
+        let piece_type = match (self, piece.piece_type) {
+            (Variant::Kyotoshogi, PieceType::ProPawn) => PieceType::Rook,
+            (Variant::Kyotoshogi, PieceType::ProSilver) => PieceType::Bishop,
+            (Variant::Kyotoshogi, PieceType::ProKnight) => PieceType::Gold,
+            (Variant::Kyotoshogi, PieceType::ProLance) => PieceType::ProPawn,
+            (_, piece_type) => piece_type,
+        };
+        Piece {
+            piece_type,
+            color: piece.color,
+        }
+    }
+
+    /// Whether pieces of the color can promote when moving from or to the
+    /// square. Kyoto shogi has no zone, as pieces flip on every move.
+    pub fn in_promotion_zone(self, color: Color, sq: Square) -> bool {
+        let zone = match self {
+            Variant::Standard => 3,
+            Variant::Minishogi => 1,
+            Variant::Kyotoshogi => return false,
+        };
+        match color {
+            Color::Black => usize::from(sq.rank()) < zone,
+            Color::White => usize::from(sq.rank()) >= self.size() - zone,
+        }
+    }
+
+    /// Letters of the pieces that may be on the board and in hand.
+    fn sfen_letters(self) -> (&'static str, &'static str) {
+        match self {
+            Variant::Standard => ("kgsnlbrp", "gsnlbrp"),
+            Variant::Minishogi => ("kgsbrp", "gsbrp"),
+            Variant::Kyotoshogi => ("ksnlp", "snlp"),
+        }
+    }
+
+    pub fn check_square(self, sq: Square) -> Result<Square, RequestError> {
+        if self.contains(sq) {
+            Ok(sq)
+        } else {
+            Err(RequestError::NotInVariant(sq.to_string(), self))
+        }
+    }
+
+    pub fn check_hand_piece(self, piece: Piece) -> Result<Piece, RequestError> {
+        if self.hand_piece_types().contains(&piece.piece_type) {
+            Ok(piece)
+        } else {
+            Err(RequestError::NotInVariant(format!("{}*", piece), self))
+        }
+    }
+
+    pub fn parse_sfen(self, sfen: &str) -> Result<Position, RequestError> {
+        let invalid = || RequestError::InvalidSfen(sfen.to_owned());
+        if self == Variant::Standard {
+            return Position::from_sfen(sfen).map_err(|_| invalid());
+        }
+
+        let (board, rest) = sfen.trim().split_once(' ').ok_or_else(invalid)?;
+        let hand = rest.split_whitespace().nth(1).unwrap_or("-");
+        let (board_letters, hand_letters) = self.sfen_letters();
+        let known = |letters: &str, c: char| !c.is_ascii_alphabetic() || letters.contains(c.to_ascii_lowercase());
+        if !board.chars().all(|c| known(board_letters, c)) || !hand.chars().all(|c| known(hand_letters, c)) {
+            return Err(invalid());
+        }
+
+        let padded = pad_sfen(board).ok_or_else(invalid)?;
+        Position::from_sfen(&format!("{} {}", padded, rest)).map_err(|_| invalid())
+    }
+
+    pub fn to_sfen(self, pos: &Position) -> String {
+        match self {
+            Variant::Standard => pos.to_sfen(),
+            _ => SmallBoard::from_position(self, pos).to_sfen(),
+        }
+    }
+
+    /// Parses a USI move. Kyoto shogi pieces can be dropped with their
+    /// promoted face up, like `+L*3c`.
+    pub fn parse_move(self, usi: &str) -> Option<Move> {
+        match (self, usi.strip_prefix('+')) {
+            (Variant::Kyotoshogi, Some(usi)) => match Move::from_sfen(usi)? {
+                Move::Drop { to, piece_type } => Some(Move::Drop {
+                    to,
+                    piece_type: piece_type.promote()?,
+                }),
+                Move::Normal { .. } => None,
+            },
+            _ => Move::from_sfen(usi),
+        }
+    }
+
+    pub fn move_to_usi(self, m: Move) -> String {
+        match m {
+            Move::Drop { to, piece_type } if piece_type.is_promoted() => format!(
+                "+{}",
+                Move::Drop {
+                    to,
+                    piece_type: piece_type.unpromote().expect("promoted piece"),
+                }
+            ),
+            m => m.to_string(),
+        }
+    }
+
+    /// Plays a move, returning `false` if it is illegal.
+    ///
+    /// Kyoto shogi pieces flip on every move, whether or not the move is
+    /// marked as a promotion. They may be dropped with either face up, and
+    /// the rules about pawn drops and pieces that cannot move do not apply.
+    pub fn make_move(self, pos: &mut Position, m: Move) -> bool {
+        if self == Variant::Standard {
+            return pos.make_move(m).is_ok();
+        }
+
+        let mut board = SmallBoard::from_position(self, pos);
+        if !board.make_move(m) {
+            return false;
+        }
+        match self.parse_sfen(&board.to_sfen()) {
+            Ok(next) => {
+                *pos = next;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    pub fn in_check(self, pos: &Position) -> bool {
+        match self {
+            Variant::Standard => pos.in_check(pos.side_to_move()),
+            _ => SmallBoard::from_position(self, pos).in_check(pos.side_to_move()),
+        }
+    }
+}
+
+/// Places the ranks of a small board in the top right corner of a
+/// standard board.
+fn pad_sfen(board: &str) -> Option<String> {
+    let ranks: Vec<&str> = board.split('/').collect();
+    if ranks.len() != SMALL_SIZE {
+        return None;
+    }
+
+    let mut padded = Vec::with_capacity(9);
+    for rank in ranks {
+        let width: u32 = rank
+            .chars()
+            .map(|c| c.to_digit(10).unwrap_or(u32::from(c.is_ascii_alphabetic())))
+            .sum();
+        if width as usize != SMALL_SIZE {
+            return None;
+        }
+        // Empty files 9 to 6 in front of the rank.
+        padded.push(match rank.chars().next().and_then(|c| c.to_digit(10)) {
+            Some(empty) => format!("{}{}", empty + 4, &rank[1..]),
+            None => format!("4{}", rank),
+        });
+    }
+    padded.resize(9, "9".to_owned());
+    Some(padded.join("/"))
+}
+
+/// File and rank offsets for sente, who moves towards rank a.
+type Offsets = &'static [(i8, i8)];
+
+const KING_STEPS: Offsets = &[(0, -1), (1, -1), (-1, -1), (1, 0), (-1, 0), (0, 1), (1, 1), (-1, 1)];
+const GOLD_STEPS: Offsets = &[(0, -1), (1, -1), (-1, -1), (1, 0), (-1, 0), (0, 1)];
+const SILVER_STEPS: Offsets = &[(0, -1), (1, -1), (-1, -1), (1, 1), (-1, 1)];
+const KNIGHT_STEPS: Offsets = &[(1, -2), (-1, -2)];
+const FORWARD: Offsets = &[(0, -1)];
+const DIAGONALS: Offsets = &[(1, -1), (-1, -1), (1, 1), (-1, 1)];
+const ORTHOGONALS: Offsets = &[(0, -1), (1, 0), (-1, 0), (0, 1)];
+
+/// Single steps and sliding directions of a piece type.
+fn movement(piece_type: PieceType) -> (Offsets, Offsets) {
+    match piece_type {
+        PieceType::King => (KING_STEPS, &[]),
+        PieceType::Gold | PieceType::ProPawn | PieceType::ProLance | PieceType::ProKnight | PieceType::ProSilver => {
+            (GOLD_STEPS, &[])
+        }
+        PieceType::Silver => (SILVER_STEPS, &[]),
+        PieceType::Knight => (KNIGHT_STEPS, &[]),
+        PieceType::Lance => (&[], FORWARD),
+        PieceType::Pawn => (FORWARD, &[]),
+        PieceType::Bishop => (&[], DIAGONALS),
+        PieceType::Rook => (&[], ORTHOGONALS),
+        PieceType::ProBishop => (ORTHOGONALS, DIAGONALS),
+        PieceType::ProRook => (DIAGONALS, ORTHOGONALS),
+    }
+}
+
+/// Position on a small board that moves can be played on without the move
+/// generation of a standard position.
+#[derive(Clone)]
+struct SmallBoard {
+    variant: Variant,
+    /// Pieces by rank, then by file.
+    board: [[Option<Piece>; SMALL_SIZE]; SMALL_SIZE],
+    /// Pieces in hand, in SFEN order.
+    hand: Vec<(Piece, u8)>,
+    side_to_move: Color,
+    ply: u16,
+}
+
+impl SmallBoard {
+    fn from_position(variant: Variant, pos: &Position) -> SmallBoard {
+        let mut board = [[None; SMALL_SIZE]; SMALL_SIZE];
+        for sq in variant.squares() {
+            board[usize::from(sq.rank())][usize::from(sq.file())] = *pos.piece_at(sq);
+        }
+        SmallBoard {
+            variant,
+            board,
+            hand: variant
+                .hand_pieces()
+                .into_iter()
+                .map(|piece| (piece, pos.hand(piece)))
+                .collect(),
+            side_to_move: pos.side_to_move(),
+            ply: pos.ply(),
+        }
+    }
+
+    fn piece(&self, sq: Square) -> Option<Piece> {
+        self.board[usize::from(sq.rank())][usize::from(sq.file())]
+    }
+
+    fn at(&mut self, sq: Square) -> &mut Option<Piece> {
+        &mut self.board[usize::from(sq.rank())][usize::from(sq.file())]
+    }
+
+    fn hand(&mut self, piece_type: PieceType) -> Option<&mut u8> {
+        let color = self.side_to_move;
+        self.hand
+            .iter_mut()
+            .find(|(p, _)| p.piece_type == piece_type && p.color == color)
+            .map(|(_, nb)| nb)
+    }
+
+    fn in_promotion_zone(&self, sq: Square) -> bool {
+        self.variant.in_promotion_zone(self.side_to_move, sq)
+    }
+
+    /// The square one step away from `sq`, in a direction given for sente.
+    fn offset(&self, sq: Square, color: Color, (file, rank): (i8, i8)) -> Option<Square> {
+        let (file, rank) = match color {
+            Color::Black => (file, rank),
+            Color::White => (-file, -rank),
+        };
+        let file = u8::try_from(sq.file() as i8 + file).ok()?;
+        let rank = u8::try_from(sq.rank() as i8 + rank).ok()?;
+        Square::new(file, rank).filter(|&sq| self.variant.contains(sq))
+    }
+
+    /// Whether the piece on `from` can reach `to`, moving like its face.
+    fn attacks(&self, from: Square, to: Square) -> bool {
+        let piece = match self.piece(from) {
+            Some(piece) => self.variant.face(piece),
+            None => return false,
+        };
+        let (steps, slides) = movement(piece.piece_type);
+        steps.iter().any(|&d| self.offset(from, piece.color, d) == Some(to))
+            || slides.iter().any(|&d| {
+                let mut sq = from;
+                while let Some(next) = self.offset(sq, piece.color, d) {
+                    if next == to {
+                        return true;
+                    }
+                    if self.piece(next).is_some() {
+                        return false;
+                    }
+                    sq = next;
+                }
+                false
+            })
+    }
+
+    /// Whether the piece has any move from the square on an empty board.
+    fn can_move(&self, piece: Piece, sq: Square) -> bool {
+        let piece = self.variant.face(piece);
+        let (steps, slides) = movement(piece.piece_type);
+        steps
+            .iter()
+            .chain(slides)
+            .any(|&d| self.offset(sq, piece.color, d).is_some())
+    }
+
+    fn in_check(&self, color: Color) -> bool {
+        let king = Piece {
+            piece_type: PieceType::King,
+            color,
+        };
+        let squares = self.variant.squares();
+        match squares.into_iter().find(|&sq| self.piece(sq) == Some(king)) {
+            Some(king) => squares
+                .into_iter()
+                .any(|sq| self.piece(sq).is_some_and(|p| p.color != color) && self.attacks(sq, king)),
+            None => false,
+        }
+    }
+
+    /// Plays the move if it is legal.
+    fn make_move(&mut self, m: Move) -> bool {
+        let next = match self.play(m) {
+            Some(next) => next,
+            None => return false,
+        };
+        let pawn_drop = matches!(
+            m,
+            Move::Drop {
+                piece_type: PieceType::Pawn,
+                ..
+            }
+        );
+        if pawn_drop
+            && self.variant != Variant::Kyotoshogi
+            && next.in_check(next.side_to_move)
+            && !next.has_legal_move()
+        {
+            return false;
+        }
+        *self = next;
+        true
+    }
+
+    /// The position after the move, if it is legal apart from the ban on
+    /// mating with a pawn drop.
+    fn play(&self, m: Move) -> Option<SmallBoard> {
+        let side_to_move = self.side_to_move;
+        let kyotoshogi = self.variant == Variant::Kyotoshogi;
+        let mut next = self.clone();
+        match m {
+            Move::Normal { from, to, promote } => {
+                if !self.variant.contains(from) || !self.variant.contains(to) {
+                    return None;
+                }
+                let piece = self.piece(from).filter(|p| p.color == side_to_move)?;
+                if self.piece(to).is_some_and(|p| p.color == side_to_move) || !self.attacks(from, to) {
+                    return None;
+                }
+                let piece = if kyotoshogi {
+                    if piece.piece_type.is_promoted() {
+                        piece.unpromote().unwrap_or(piece)
+                    } else {
+                        piece.promote().unwrap_or(piece)
+                    }
+                } else if promote {
+                    match piece.promote() {
+                        Some(promoted) if self.in_promotion_zone(from) || self.in_promotion_zone(to) => promoted,
+                        _ => return None,
+                    }
+                } else if self.can_move(piece, to) {
+                    piece
+                } else {
+                    return None;
+                };
+                if let Some(captured) = self.piece(to) {
+                    let piece_type = captured.piece_type.unpromote().unwrap_or(captured.piece_type);
+                    *next.hand(piece_type)? += 1;
+                }
+                *next.at(from) = None;
+                *next.at(to) = Some(piece);
+            }
+            Move::Drop { to, piece_type } => {
+                if !self.variant.contains(to) || self.piece(to).is_some() {
+                    return None;
+                }
+                let held = match piece_type.unpromote() {
+                    Some(held) if kyotoshogi => held,
+                    _ => piece_type,
+                };
+                match next.hand(held) {
+                    Some(nb) if *nb > 0 => *nb -= 1,
+                    _ => return None,
+                }
+                let piece = Piece {
+                    piece_type,
+                    color: side_to_move,
+                };
+                if !kyotoshogi {
+                    let nifu = piece_type == PieceType::Pawn
+                        && self
+                            .variant
+                            .squares()
+                            .any(|sq| sq.file() == to.file() && self.piece(sq) == Some(piece));
+                    if nifu || !self.can_move(piece, to) {
+                        return None;
+                    }
+                }
+                *next.at(to) = Some(piece);
+            }
+        }
+        if next.in_check(side_to_move) {
+            return None;
+        }
+        next.side_to_move = side_to_move.flip();
+        next.ply += 1;
+        Some(next)
+    }
+
+    /// Whether the side to move has any legal move, not counting drops
+    /// with the promoted face up.
+    fn has_legal_move(&self) -> bool {
+        let color = self.side_to_move;
+        let squares = self.variant.squares();
+        let moves = squares
+            .into_iter()
+            .filter(|&from| self.piece(from).is_some_and(|p| p.color == color))
+            .flat_map(|from| squares.into_iter().map(move |to| (from, to)))
+            .flat_map(|(from, to)| [false, true].map(|promote| Move::Normal { from, to, promote }));
+        let drops = self
+            .hand
+            .iter()
+            .filter(|(p, nb)| p.color == color && *nb > 0)
+            .flat_map(|(p, _)| {
+                squares
+                    .into_iter()
+                    .filter(|&to| self.piece(to).is_none())
+                    .map(move |to| Move::Drop {
+                        to,
+                        piece_type: p.piece_type,
+                    })
+            });
+        moves.chain(drops).any(|m| self.play(m).is_some())
+    }
+
+    fn to_sfen(&self) -> String {
+        let mut sfen = String::new();
+        for (rank, pieces) in self.board.iter().enumerate() {
+            if rank > 0 {
+                sfen.push('/');
+            }
+            let mut empty = 0;
+            // Files are written from left to right, starting at file 5.
+            for piece in pieces.iter().rev() {
+                match piece {
+                    Some(piece) => {
+                        if empty > 0 {
+                            sfen.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        sfen.push_str(&piece.to_string());
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                sfen.push_str(&empty.to_string());
+            }
+        }
+
+        sfen.push_str(match self.side_to_move {
+            Color::Black => " b ",
+            Color::White => " w ",
+        });
+
+        let hand: String = self
+            .hand
+            .iter()
+            .filter(|(_, nb)| *nb > 0)
+            .map(|(piece, nb)| match *nb {
+                1 => piece.to_string(),
+                nb => format!("{}{}", nb, piece),
+            })
+            .collect();
+        sfen.push_str(if hand.is_empty() { "-" } else { &hand });
+
+        sfen.push_str(&format!(" {}", self.ply));
+        sfen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// SFEN after playing the move, or `None` if it is illegal.
+    fn play(variant: Variant, sfen: &str, usi: &str) -> Option<String> {
+        let mut pos = variant.parse_sfen(sfen).unwrap();
+        let m = variant.parse_move(usi)?;
+        if variant.make_move(&mut pos, m) {
+            Some(variant.to_sfen(&pos))
+        } else {
+            None
+        }
+    }
+
+    #[test]
+    fn test_padding() {
+        let sfen = Variant::Minishogi.starting_position();
+        let pos = Variant::Minishogi.parse_sfen(sfen).unwrap();
+        assert_eq!(pos.to_sfen(), "4rbsgk/8p/9/4P4/4KGSBR/9/9/9/9 b - 1");
+        assert_eq!(Variant::Minishogi.to_sfen(&pos), sfen);
+
+        assert!(Variant::Minishogi.parse_sfen("rbsgk/4p/5/P4 b - 1").is_err());
+        assert!(Variant::Minishogi.parse_sfen("rbsgk/4p/6/P4/KGSBR b - 1").is_err());
+        assert!(Variant::Minishogi.parse_sfen("rbsgk/4p/5/P4/KGSBR b N 1").is_err());
+    }
+
+    #[test]
+    fn test_coordinates() {
+        let m = Variant::Minishogi.parse_move("5e5d").unwrap();
+        assert_eq!(
+            m,
+            Move::Normal {
+                from: Square::new(4, 4).unwrap(),
+                to: Square::new(4, 3).unwrap(),
+                promote: false,
+            }
+        );
+        assert_eq!(Variant::Minishogi.move_to_usi(m), "5e5d");
+        assert!(Variant::Minishogi.check_square(Square::new(4, 4).unwrap()).is_ok());
+        assert!(matches!(
+            Variant::Minishogi.check_square(Square::new(5, 0).unwrap()),
+            Err(RequestError::NotInVariant(name, Variant::Minishogi)) if name == "6a"
+        ));
+    }
+
+    #[test]
+    fn test_piece_movement() {
+        let start = Variant::Minishogi.starting_position();
+        assert_eq!(
+            play(Variant::Minishogi, start, "1e1b").as_deref(),
+            Some("rbsgk/4R/5/P4/KGSB1 w P 2")
+        );
+        assert_eq!(play(Variant::Minishogi, start, "1e1a"), None);
+        assert_eq!(play(Variant::Minishogi, start, "4e3c"), None);
+        assert_eq!(play(Variant::Minishogi, start, "5d5c+"), None);
+
+        // The pawn cannot stay unpromoted on the last rank.
+        let sfen = "rbsgk/P4/5/5/KGSBR b - 1";
+        assert_eq!(play(Variant::Minishogi, sfen, "5b5a"), None);
+        assert_eq!(
+            play(Variant::Minishogi, sfen, "5b5a+").as_deref(),
+            Some("+Pbsgk/5/5/5/KGSBR w R 2")
+        );
+    }
+
+    #[test]
+    fn test_own_king_in_check() {
+        let sfen = "4k/5/5/5/K3r b - 1";
+        assert!(Variant::Minishogi.in_check(&Variant::Minishogi.parse_sfen(sfen).unwrap()));
+        assert_eq!(play(Variant::Minishogi, sfen, "5e4e"), None);
+        assert_eq!(
+            play(Variant::Minishogi, sfen, "5e4d").as_deref(),
+            Some("4k/5/5/1K3/4r w - 2")
+        );
+    }
+
+    #[test]
+    fn test_pawn_drops() {
+        let sfen = "rbsgk/5/5/P4/KGSBR b P 1";
+        assert_eq!(play(Variant::Minishogi, sfen, "P*5b"), None);
+        assert!(play(Variant::Minishogi, sfen, "P*4c").is_some());
+
+        // Mating with a pawn drop is not allowed, but checking is.
+        assert_eq!(play(Variant::Minishogi, "4k/5/1B1G1/5/K4 b P 1", "P*1b"), None);
+        assert_eq!(
+            play(Variant::Minishogi, "4k/5/3G1/5/K4 b P 1", "P*1b").as_deref(),
+            Some("4k/4P/3G1/5/K4 w - 2")
+        );
+    }
+
+    #[test]
+    fn test_kyotoshogi_flips() {
+        let start = Variant::Kyotoshogi.starting_position();
+        assert_eq!(
+            play(Variant::Kyotoshogi, start, "2e2d").as_deref(),
+            Some("p+nks+l/5/5/3N1/+LSK1P w - 2")
+        );
+        assert_eq!(
+            play(Variant::Kyotoshogi, start, "4e4d+").as_deref(),
+            Some("p+nks+l/5/5/1+S3/+L1K+NP w - 2")
+        );
+        assert_eq!(play(Variant::Kyotoshogi, start, "1e1c"), None);
+        assert_eq!(play(Variant::Kyotoshogi, start, "4e4c"), None);
+    }
+
+    #[test]
+    fn test_kyotoshogi_drops() {
+        let m = Variant::Kyotoshogi.parse_move("+L*3c").unwrap();
+        assert_eq!(
+            m,
+            Move::Drop {
+                to: Square::new(2, 2).unwrap(),
+                piece_type: PieceType::ProLance,
+            }
+        );
+        assert_eq!(Variant::Kyotoshogi.move_to_usi(m), "+L*3c");
+        assert_eq!(Variant::Kyotoshogi.parse_move("+5e5d"), None);
+
+        let sfen = "p+nks+l/5/5/5/1SK+NP b L 1";
+        assert_eq!(
+            play(Variant::Kyotoshogi, sfen, "+L*3c").as_deref(),
+            Some("p+nks+l/5/2+L2/5/1SK+NP w - 2")
+        );
+        assert_eq!(
+            play(Variant::Kyotoshogi, sfen, "L*3c").as_deref(),
+            Some("p+nks+l/5/2L2/5/1SK+NP w - 2")
+        );
+        assert_eq!(play(Variant::Kyotoshogi, sfen, "N*3c"), None);
+    }
+
+    #[test]
+    fn test_kyotoshogi_check_by_face() {
+        // A promoted pawn is a rook in kyoto shogi, but a tokin in minishogi.
+        let sfen = "2k2/5/5/5/K1+P2 w - 1";
+        assert!(Variant::Kyotoshogi.in_check(&Variant::Kyotoshogi.parse_sfen(sfen).unwrap()));
+        assert!(!Variant::Minishogi.in_check(&Variant::Minishogi.parse_sfen(sfen).unwrap()));
+        let sfen = "2k2/5/5/5/K1P2 w - 1";
+        assert!(!Variant::Kyotoshogi.in_check(&Variant::Kyotoshogi.parse_sfen(sfen).unwrap()));
+    }
+}
@@ -0,0 +1,339 @@
+# Ryoko piece shapes with Latin letters, outlined from Noto Sans, and
+# marks for the squares each piece can move to.
+
+PIECE_BACKGROUND = """<linearGradient id="a">
+        <stop offset="0" stop-color="#f9da9c" />
+        <stop offset=".043" stop-color="#fbe0a1" />
+        <stop offset=".067" stop-color="#fce6a6" />
+        <stop offset=".145" stop-color="#f4d593" />
+        <stop offset=".193" stop-color="#f5d797" />
+        <stop offset=".243" stop-color="#f7dda4" />
+        <stop offset=".263" stop-color="#f9e1ab" />
+        <stop offset=".329" stop-color="#f7dfa8" />
+        <stop offset=".383" stop-color="#f2d89e" />
+        <stop offset=".398" stop-color="#efd599" />
+        <stop offset=".461" stop-color="#f2d795" />
+        <stop offset=".462" stop-color="#f2d795" />
+        <stop offset=".527" stop-color="#f2d795" />
+        <stop offset=".538" stop-color="#f2d795" />
+        <stop offset=".538" stop-color="#f2d795" />
+        <stop offset=".615" stop-color="#efda9c" />
+        <stop offset=".735" stop-color="#f6e5ad" />
+        <stop offset=".773" stop-color="#f9e9b4" />
+        <stop offset=".822" stop-color="#f7e1a6" />
+        <stop offset=".887" stop-color="#f5db9b" />
+        <stop offset=".956" stop-color="#f4d997" />
+        <stop offset=".967" stop-color="#f4da9c" />
+        <stop offset="1" stop-color="#f4db9e"/>
+    </linearGradient>"""
+
+PIECES = {
+"K": """<g id="black-king">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M56.95 65.50L54.13 65.50L47.38 56.43L45.44 58.13L45.44 65.50L43.05 65.50L43.05 46.50L45.44 46.50L45.44 55.87L47.08 54.06L53.85 46.50L56.65 46.50L49.12 54.78L56.95 65.50Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="56.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="50.0" cy="73.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="56.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="73.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="73.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+</g>""",
+"GY": """<g id="black-tama">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M56.95 65.50L54.13 65.50L47.38 56.43L45.44 58.13L45.44 65.50L43.05 65.50L43.05 46.50L45.44 46.50L45.44 55.87L47.08 54.06L53.85 46.50L56.65 46.50L49.12 54.78L56.95 65.50Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="56.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="50.0" cy="73.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="56.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="73.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="73.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+</g>""",
+"R": """<g id="black-rook">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M45.61 55.62L48.45 55.62Q50.77 55.62 51.83 54.71Q52.90 53.80 52.90 52.01Q52.90 50.22 51.82 49.39Q50.74 48.57 48.32 48.57L45.61 48.57L45.61 55.62ZM45.61 57.65L45.61 65.50L43.22 65.50L43.22 46.50L48.45 46.50Q51.99 46.50 53.68 47.85Q55.38 49.19 55.38 51.89Q55.38 55.68 51.57 56.99L56.78 65.50L53.99 65.50L49.39 57.65L45.61 57.65Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width=".8" />
+    <path d="M50.0 44.1L50.0 34.8" stroke="#1a1a1a" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M61.9 56.0L71.2 56.0" stroke="#1a1a1a" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M50.0 67.9L50.0 77.2" stroke="#1a1a1a" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M38.1 56.0L28.8 56.0" stroke="#1a1a1a" stroke-width="3.4" stroke-linecap="round" />
+</g>""",
+"B": """<g id="black-bishop">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M43.36 46.50L48.78 46.50Q52.60 46.50 54.30 47.64Q55.99 48.77 55.99 51.20Q55.99 52.89 55.07 53.99Q54.15 55.09 52.38 55.42L52.38 55.55Q54.57 55.92 55.61 57.07Q56.64 58.21 56.64 60.09Q56.64 62.64 54.90 64.07Q53.16 65.50 50.04 65.50L43.36 65.50L43.36 46.50ZM45.75 54.58L49.26 54.58Q51.49 54.58 52.51 53.87Q53.52 53.15 53.52 51.48Q53.52 49.92 52.41 49.23Q51.30 48.54 48.90 48.54L45.75 48.54L45.75 54.58ZM45.75 56.58L45.75 63.47L49.58 63.47Q51.87 63.47 52.98 62.59Q54.08 61.71 54.08 59.91Q54.08 58.21 52.91 57.40Q51.74 56.58 49.40 56.58L45.75 56.58Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width=".8" />
+    <path d="M61.9 44.1L71.2 34.8" stroke="#1a1a1a" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M61.9 67.9L71.2 77.2" stroke="#1a1a1a" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M38.1 67.9L28.8 77.2" stroke="#1a1a1a" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M38.1 44.1L28.8 34.8" stroke="#1a1a1a" stroke-width="3.4" stroke-linecap="round" />
+</g>""",
+"G": """<g id="black-gold">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".92" stroke-miterlimit="10" d="M74.4 17.5 50 8.7l-24.4 8.8L16.2 84v7.3h67.6V84z" />
+    <path fill="#63451a" fill-opacity=".75" d="M16.7 84h66.7v6.8H16.7z" />
+    <path d="M51.26 55.50L57.47 55.50L57.47 64.32Q56.05 64.78 54.57 65.01Q53.09 65.25 51.15 65.25Q47.03 65.25 44.78 62.81Q42.53 60.37 42.53 56.00Q42.53 53.18 43.65 51.08Q44.77 48.97 46.88 47.86Q48.99 46.74 51.86 46.74Q54.74 46.74 57.23 47.80L56.37 49.77Q53.99 48.74 51.72 48.74Q48.51 48.74 46.70 50.68Q44.89 52.63 44.89 56.00Q44.89 59.57 46.63 61.43Q48.36 63.29 51.67 63.29Q53.48 63.29 55.21 62.86L55.21 57.51L51.26 57.51L51.26 55.50Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="56.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="56.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="50.0" cy="73.0" r="2.6" fill="#1a1a1a" />
+</g>""",
+"S": """<g id="black-silver">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".922" stroke-miterlimit="10" d="M74.5 17.5 50 8.6l-24.5 8.9-9.4 66.6v7.3h67.8v-7.3z" />
+    <path fill="#63451a" fill-opacity=".75" d="M16.6 84.1h66.8v6.8H16.6z" />
+    <path d="M55.69 60.19Q55.69 62.54 53.99 63.89Q52.29 65.25 49.26 65.25Q46.06 65.25 44.31 64.40L44.31 62.23Q45.46 62.72 46.82 63.01Q48.18 63.29 49.36 63.29Q51.38 63.29 52.40 62.51Q53.42 61.74 53.42 60.38Q53.42 59.48 53.05 58.91Q52.68 58.33 51.82 57.82Q50.95 57.32 49.19 56.68Q46.69 55.77 45.62 54.53Q44.55 53.29 44.55 51.33Q44.55 49.23 46.13 47.98Q47.71 46.74 50.30 46.74Q52.99 46.74 55.26 47.75L54.56 49.70Q52.24 48.74 50.25 48.74Q48.65 48.74 47.74 49.43Q46.84 50.11 46.84 51.36Q46.84 52.24 47.19 52.83Q47.54 53.41 48.32 53.89Q49.10 54.37 50.78 54.99Q52.78 55.73 53.77 56.42Q54.75 57.12 55.22 58.03Q55.69 58.93 55.69 60.19Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="73.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="73.0" r="2.6" fill="#1a1a1a" />
+</g>""",
+"N": """<g id="black-knight">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".924" stroke-miterlimit="10" d="M73.5 19.4 50 11l-23.5 8.4-9 63V89h65v-6.6z" />
+    <path fill="#63451a" fill-opacity=".75" d="M18 82.4h64v6H18z" />
+    <path d="M56.34 64.00L53.99 64.00L45.42 50.71L45.33 50.71L45.39 51.60Q45.52 53.59 45.52 55.06L45.52 64.00L43.66 64.00L43.66 48.00L45.99 48.00L46.48 48.78L51.73 56.90L54.53 61.24L54.61 61.24Q54.59 60.98 54.53 59.37Q54.46 57.75 54.46 57.03L54.46 48.00L56.34 48.00L56.34 64.00Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width=".8" />
+    <circle cx="39.8" cy="30.5" r="2.6" fill="#1a1a1a" />
+    <circle cx="60.2" cy="30.5" r="2.6" fill="#1a1a1a" />
+</g>""",
+"L": """<g id="black-lance">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".924" stroke-miterlimit="10" d="M73.5 19.4 50 11l-23.5 8.4-9 63V89h65v-6.6z" />
+    <path fill="#63451a" fill-opacity=".75" d="M18 82.4h64v6H18z" />
+    <path d="M45.50 64.00L45.50 48.00L47.51 48.00L47.51 62.21L54.50 62.21L54.50 64.00L45.50 64.00Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width=".8" />
+    <path d="M50.0 44.1L50.0 34.8" stroke="#1a1a1a" stroke-width="3.4" stroke-linecap="round" />
+</g>""",
+"P": """<g id="black-pawn">
+    <path d="M72.6 21.1 50 12.9l-22.6 8.2-8.7 61v5h62.6v-5z" style="fill:url(#a);stroke:#63451a;stroke-width:.94400001;stroke-miterlimit:10" />
+    <path d="M19.2 82.1h61.6v4.5H19.2z" style="opacity:.75;fill:#63451a" />
+    <path d="M55.12 52.71Q55.12 55.16 53.45 56.47Q51.77 57.77 48.73 57.77L46.89 57.77L46.89 64.00L44.88 64.00L44.88 48.00L49.11 48.00Q55.12 48.00 55.12 52.71ZM46.89 56.04L48.50 56.04Q50.89 56.04 51.96 55.27Q53.03 54.50 53.03 52.79Q53.03 51.25 52.04 50.48Q51.04 49.72 48.93 49.72L46.89 49.72L46.89 56.04Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+</g>""",
+"+R": """<g id="black-dragon">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M45.61 55.62L48.45 55.62Q50.77 55.62 51.83 54.71Q52.90 53.80 52.90 52.01Q52.90 50.22 51.82 49.39Q50.74 48.57 48.32 48.57L45.61 48.57L45.61 55.62ZM45.61 57.65L45.61 65.50L43.22 65.50L43.22 46.50L48.45 46.50Q51.99 46.50 53.68 47.85Q55.38 49.19 55.38 51.89Q55.38 55.68 51.57 56.99L56.78 65.50L53.99 65.50L49.39 57.65L45.61 57.65Z" fill="#c30" stroke="#c30" stroke-width=".8" />
+    <circle cx="67.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="73.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="73.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="39.0" r="2.6" fill="#c30" />
+    <path d="M50.0 44.1L50.0 34.8" stroke="#c30" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M61.9 56.0L71.2 56.0" stroke="#c30" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M50.0 67.9L50.0 77.2" stroke="#c30" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M38.1 56.0L28.8 56.0" stroke="#c30" stroke-width="3.4" stroke-linecap="round" />
+</g>""",
+"+B": """<g id="black-horse">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M43.36 46.50L48.78 46.50Q52.60 46.50 54.30 47.64Q55.99 48.77 55.99 51.20Q55.99 52.89 55.07 53.99Q54.15 55.09 52.38 55.42L52.38 55.55Q54.57 55.92 55.61 57.07Q56.64 58.21 56.64 60.09Q56.64 62.64 54.90 64.07Q53.16 65.50 50.04 65.50L43.36 65.50L43.36 46.50ZM45.75 54.58L49.26 54.58Q51.49 54.58 52.51 53.87Q53.52 53.15 53.52 51.48Q53.52 49.92 52.41 49.23Q51.30 48.54 48.90 48.54L45.75 48.54L45.75 54.58ZM45.75 56.58L45.75 63.47L49.58 63.47Q51.87 63.47 52.98 62.59Q54.08 61.71 54.08 59.91Q54.08 58.21 52.91 57.40Q51.74 56.58 49.40 56.58L45.75 56.58Z" fill="#c30" stroke="#c30" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="56.0" r="2.6" fill="#c30" />
+    <circle cx="50.0" cy="73.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="56.0" r="2.6" fill="#c30" />
+    <path d="M61.9 44.1L71.2 34.8" stroke="#c30" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M61.9 67.9L71.2 77.2" stroke="#c30" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M38.1 67.9L28.8 77.2" stroke="#c30" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M38.1 44.1L28.8 34.8" stroke="#c30" stroke-width="3.4" stroke-linecap="round" />
+</g>""",
+"+S": """<g id="black-prosilver">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".92" stroke-miterlimit="10" d="M74.4 17.5 50 8.7l-24.4 8.8L16.2 84v7.3h67.6V84z" />
+    <path fill="#63451a" fill-opacity=".75" d="M16.7 84h66.7v6.8H16.7z" />
+    <path d="M55.69 60.19Q55.69 62.54 53.99 63.89Q52.29 65.25 49.26 65.25Q46.06 65.25 44.31 64.40L44.31 62.23Q45.46 62.72 46.82 63.01Q48.18 63.29 49.36 63.29Q51.38 63.29 52.40 62.51Q53.42 61.74 53.42 60.38Q53.42 59.48 53.05 58.91Q52.68 58.33 51.82 57.82Q50.95 57.32 49.19 56.68Q46.69 55.77 45.62 54.53Q44.55 53.29 44.55 51.33Q44.55 49.23 46.13 47.98Q47.71 46.74 50.30 46.74Q52.99 46.74 55.26 47.75L54.56 49.70Q52.24 48.74 50.25 48.74Q48.65 48.74 47.74 49.43Q46.84 50.11 46.84 51.36Q46.84 52.24 47.19 52.83Q47.54 53.41 48.32 53.89Q49.10 54.37 50.78 54.99Q52.78 55.73 53.77 56.42Q54.75 57.12 55.22 58.03Q55.69 58.93 55.69 60.19Z" fill="#c30" stroke="#c30" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="56.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="56.0" r="2.6" fill="#c30" />
+    <circle cx="50.0" cy="73.0" r="2.6" fill="#c30" />
+</g>""",
+"+N": """<g id="black-proknight">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".924" stroke-miterlimit="10" d="M73.5 19.4 50 11l-23.5 8.4-9 63V89h65v-6.6z" />
+    <path fill="#63451a" fill-opacity=".75" d="M18 82.4h64v6H18z" />
+    <path d="M56.34 64.00L53.99 64.00L45.42 50.71L45.33 50.71L45.39 51.60Q45.52 53.59 45.52 55.06L45.52 64.00L43.66 64.00L43.66 48.00L45.99 48.00L46.48 48.78L51.73 56.90L54.53 61.24L54.61 61.24Q54.59 60.98 54.53 59.37Q54.46 57.75 54.46 57.03L54.46 48.00L56.34 48.00L56.34 64.00Z" fill="#c30" stroke="#c30" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="56.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="56.0" r="2.6" fill="#c30" />
+    <circle cx="50.0" cy="73.0" r="2.6" fill="#c30" />
+</g>""",
+"+L": """<g id="black-prolance">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".924" stroke-miterlimit="10" d="M73.5 19.4 50 11l-23.5 8.4-9 63V89h65v-6.6z" />
+    <path fill="#63451a" fill-opacity=".75" d="M18 82.4h64v6H18z" />
+    <path d="M45.50 64.00L45.50 48.00L47.51 48.00L47.51 62.21L54.50 62.21L54.50 64.00L45.50 64.00Z" fill="#c30" stroke="#c30" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="56.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="56.0" r="2.6" fill="#c30" />
+    <circle cx="50.0" cy="73.0" r="2.6" fill="#c30" />
+</g>""",
+"+P": """<g id="black-tokin">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".944" stroke-miterlimit="10" d="M72.6 21.1 50 12.9l-22.6 8.2-8.7 61v5h62.6v-5z" />
+    <path opacity=".75" fill="#63451a" d="M19.2 82.1h61.6v4.5H19.2z" />
+    <path d="M55.12 52.71Q55.12 55.16 53.45 56.47Q51.77 57.77 48.73 57.77L46.89 57.77L46.89 64.00L44.88 64.00L44.88 48.00L49.11 48.00Q55.12 48.00 55.12 52.71ZM46.89 56.04L48.50 56.04Q50.89 56.04 51.96 55.27Q53.03 54.50 53.03 52.79Q53.03 51.25 52.04 50.48Q51.04 49.72 48.93 49.72L46.89 49.72L46.89 56.04Z" fill="#c30" stroke="#c30" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="56.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="56.0" r="2.6" fill="#c30" />
+    <circle cx="50.0" cy="73.0" r="2.6" fill="#c30" />
+</g>""",
+"k": """<g id="white-king" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M56.95 65.50L54.13 65.50L47.38 56.43L45.44 58.13L45.44 65.50L43.05 65.50L43.05 46.50L45.44 46.50L45.44 55.87L47.08 54.06L53.85 46.50L56.65 46.50L49.12 54.78L56.95 65.50Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="56.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="50.0" cy="73.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="56.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="73.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="73.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+</g>""",
+"gy": """<g id="white-tama" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M56.95 65.50L54.13 65.50L47.38 56.43L45.44 58.13L45.44 65.50L43.05 65.50L43.05 46.50L45.44 46.50L45.44 55.87L47.08 54.06L53.85 46.50L56.65 46.50L49.12 54.78L56.95 65.50Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="56.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="50.0" cy="73.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="56.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="73.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="73.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+</g>""",
+"r": """<g id="white-rook" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M45.61 55.62L48.45 55.62Q50.77 55.62 51.83 54.71Q52.90 53.80 52.90 52.01Q52.90 50.22 51.82 49.39Q50.74 48.57 48.32 48.57L45.61 48.57L45.61 55.62ZM45.61 57.65L45.61 65.50L43.22 65.50L43.22 46.50L48.45 46.50Q51.99 46.50 53.68 47.85Q55.38 49.19 55.38 51.89Q55.38 55.68 51.57 56.99L56.78 65.50L53.99 65.50L49.39 57.65L45.61 57.65Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width=".8" />
+    <path d="M50.0 44.1L50.0 34.8" stroke="#1a1a1a" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M61.9 56.0L71.2 56.0" stroke="#1a1a1a" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M50.0 67.9L50.0 77.2" stroke="#1a1a1a" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M38.1 56.0L28.8 56.0" stroke="#1a1a1a" stroke-width="3.4" stroke-linecap="round" />
+</g>""",
+"b": """<g id="white-bishop" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M43.36 46.50L48.78 46.50Q52.60 46.50 54.30 47.64Q55.99 48.77 55.99 51.20Q55.99 52.89 55.07 53.99Q54.15 55.09 52.38 55.42L52.38 55.55Q54.57 55.92 55.61 57.07Q56.64 58.21 56.64 60.09Q56.64 62.64 54.90 64.07Q53.16 65.50 50.04 65.50L43.36 65.50L43.36 46.50ZM45.75 54.58L49.26 54.58Q51.49 54.58 52.51 53.87Q53.52 53.15 53.52 51.48Q53.52 49.92 52.41 49.23Q51.30 48.54 48.90 48.54L45.75 48.54L45.75 54.58ZM45.75 56.58L45.75 63.47L49.58 63.47Q51.87 63.47 52.98 62.59Q54.08 61.71 54.08 59.91Q54.08 58.21 52.91 57.40Q51.74 56.58 49.40 56.58L45.75 56.58Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width=".8" />
+    <path d="M61.9 44.1L71.2 34.8" stroke="#1a1a1a" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M61.9 67.9L71.2 77.2" stroke="#1a1a1a" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M38.1 67.9L28.8 77.2" stroke="#1a1a1a" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M38.1 44.1L28.8 34.8" stroke="#1a1a1a" stroke-width="3.4" stroke-linecap="round" />
+</g>""",
+"g": """<g id="white-gold" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".92" stroke-miterlimit="10" d="M74.4 17.5 50 8.7l-24.4 8.8L16.2 84v7.3h67.6V84z" />
+    <path fill="#63451a" fill-opacity=".75" d="M16.7 84h66.7v6.8H16.7z" />
+    <path d="M51.26 55.50L57.47 55.50L57.47 64.32Q56.05 64.78 54.57 65.01Q53.09 65.25 51.15 65.25Q47.03 65.25 44.78 62.81Q42.53 60.37 42.53 56.00Q42.53 53.18 43.65 51.08Q44.77 48.97 46.88 47.86Q48.99 46.74 51.86 46.74Q54.74 46.74 57.23 47.80L56.37 49.77Q53.99 48.74 51.72 48.74Q48.51 48.74 46.70 50.68Q44.89 52.63 44.89 56.00Q44.89 59.57 46.63 61.43Q48.36 63.29 51.67 63.29Q53.48 63.29 55.21 62.86L55.21 57.51L51.26 57.51L51.26 55.50Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="56.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="56.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="50.0" cy="73.0" r="2.6" fill="#1a1a1a" />
+</g>""",
+"s": """<g id="white-silver" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".922" stroke-miterlimit="10" d="M74.5 17.5 50 8.6l-24.5 8.9-9.4 66.6v7.3h67.8v-7.3z" />
+    <path fill="#63451a" fill-opacity=".75" d="M16.6 84.1h66.8v6.8H16.6z" />
+    <path d="M55.69 60.19Q55.69 62.54 53.99 63.89Q52.29 65.25 49.26 65.25Q46.06 65.25 44.31 64.40L44.31 62.23Q45.46 62.72 46.82 63.01Q48.18 63.29 49.36 63.29Q51.38 63.29 52.40 62.51Q53.42 61.74 53.42 60.38Q53.42 59.48 53.05 58.91Q52.68 58.33 51.82 57.82Q50.95 57.32 49.19 56.68Q46.69 55.77 45.62 54.53Q44.55 53.29 44.55 51.33Q44.55 49.23 46.13 47.98Q47.71 46.74 50.30 46.74Q52.99 46.74 55.26 47.75L54.56 49.70Q52.24 48.74 50.25 48.74Q48.65 48.74 47.74 49.43Q46.84 50.11 46.84 51.36Q46.84 52.24 47.19 52.83Q47.54 53.41 48.32 53.89Q49.10 54.37 50.78 54.99Q52.78 55.73 53.77 56.42Q54.75 57.12 55.22 58.03Q55.69 58.93 55.69 60.19Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="67.0" cy="73.0" r="2.6" fill="#1a1a1a" />
+    <circle cx="33.0" cy="73.0" r="2.6" fill="#1a1a1a" />
+</g>""",
+"n": """<g id="white-knight" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".924" stroke-miterlimit="10" d="M73.5 19.4 50 11l-23.5 8.4-9 63V89h65v-6.6z" />
+    <path fill="#63451a" fill-opacity=".75" d="M18 82.4h64v6H18z" />
+    <path d="M56.34 64.00L53.99 64.00L45.42 50.71L45.33 50.71L45.39 51.60Q45.52 53.59 45.52 55.06L45.52 64.00L43.66 64.00L43.66 48.00L45.99 48.00L46.48 48.78L51.73 56.90L54.53 61.24L54.61 61.24Q54.59 60.98 54.53 59.37Q54.46 57.75 54.46 57.03L54.46 48.00L56.34 48.00L56.34 64.00Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width=".8" />
+    <circle cx="39.8" cy="30.5" r="2.6" fill="#1a1a1a" />
+    <circle cx="60.2" cy="30.5" r="2.6" fill="#1a1a1a" />
+</g>""",
+"l": """<g id="white-lance" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".924" stroke-miterlimit="10" d="M73.5 19.4 50 11l-23.5 8.4-9 63V89h65v-6.6z" />
+    <path fill="#63451a" fill-opacity=".75" d="M18 82.4h64v6H18z" />
+    <path d="M45.50 64.00L45.50 48.00L47.51 48.00L47.51 62.21L54.50 62.21L54.50 64.00L45.50 64.00Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width=".8" />
+    <path d="M50.0 44.1L50.0 34.8" stroke="#1a1a1a" stroke-width="3.4" stroke-linecap="round" />
+</g>""",
+"p": """<g id="white-pawn" transform="rotate(180 50 50)">
+    <path d="M72.6 21.1 50 12.9l-22.6 8.2-8.7 61v5h62.6v-5z" style="fill:url(#a);stroke:#63451a;stroke-width:.94400001;stroke-miterlimit:10" />
+    <path d="M19.2 82.1h61.6v4.5H19.2z" style="opacity:.75;fill:#63451a" />
+    <path d="M55.12 52.71Q55.12 55.16 53.45 56.47Q51.77 57.77 48.73 57.77L46.89 57.77L46.89 64.00L44.88 64.00L44.88 48.00L49.11 48.00Q55.12 48.00 55.12 52.71ZM46.89 56.04L48.50 56.04Q50.89 56.04 51.96 55.27Q53.03 54.50 53.03 52.79Q53.03 51.25 52.04 50.48Q51.04 49.72 48.93 49.72L46.89 49.72L46.89 56.04Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#1a1a1a" />
+</g>""",
+"+r": """<g id="white-dragon" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M45.61 55.62L48.45 55.62Q50.77 55.62 51.83 54.71Q52.90 53.80 52.90 52.01Q52.90 50.22 51.82 49.39Q50.74 48.57 48.32 48.57L45.61 48.57L45.61 55.62ZM45.61 57.65L45.61 65.50L43.22 65.50L43.22 46.50L48.45 46.50Q51.99 46.50 53.68 47.85Q55.38 49.19 55.38 51.89Q55.38 55.68 51.57 56.99L56.78 65.50L53.99 65.50L49.39 57.65L45.61 57.65Z" fill="#c30" stroke="#c30" stroke-width=".8" />
+    <circle cx="67.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="73.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="73.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="39.0" r="2.6" fill="#c30" />
+    <path d="M50.0 44.1L50.0 34.8" stroke="#c30" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M61.9 56.0L71.2 56.0" stroke="#c30" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M50.0 67.9L50.0 77.2" stroke="#c30" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M38.1 56.0L28.8 56.0" stroke="#c30" stroke-width="3.4" stroke-linecap="round" />
+</g>""",
+"+b": """<g id="white-horse" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M43.36 46.50L48.78 46.50Q52.60 46.50 54.30 47.64Q55.99 48.77 55.99 51.20Q55.99 52.89 55.07 53.99Q54.15 55.09 52.38 55.42L52.38 55.55Q54.57 55.92 55.61 57.07Q56.64 58.21 56.64 60.09Q56.64 62.64 54.90 64.07Q53.16 65.50 50.04 65.50L43.36 65.50L43.36 46.50ZM45.75 54.58L49.26 54.58Q51.49 54.58 52.51 53.87Q53.52 53.15 53.52 51.48Q53.52 49.92 52.41 49.23Q51.30 48.54 48.90 48.54L45.75 48.54L45.75 54.58ZM45.75 56.58L45.75 63.47L49.58 63.47Q51.87 63.47 52.98 62.59Q54.08 61.71 54.08 59.91Q54.08 58.21 52.91 57.40Q51.74 56.58 49.40 56.58L45.75 56.58Z" fill="#c30" stroke="#c30" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="56.0" r="2.6" fill="#c30" />
+    <circle cx="50.0" cy="73.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="56.0" r="2.6" fill="#c30" />
+    <path d="M61.9 44.1L71.2 34.8" stroke="#c30" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M61.9 67.9L71.2 77.2" stroke="#c30" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M38.1 67.9L28.8 77.2" stroke="#c30" stroke-width="3.4" stroke-linecap="round" />
+    <path d="M38.1 44.1L28.8 34.8" stroke="#c30" stroke-width="3.4" stroke-linecap="round" />
+</g>""",
+"+s": """<g id="white-prosilver" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".92" stroke-miterlimit="10" d="M74.4 17.5 50 8.7l-24.4 8.8L16.2 84v7.3h67.6V84z" />
+    <path fill="#63451a" fill-opacity=".75" d="M16.7 84h66.7v6.8H16.7z" />
+    <path d="M55.69 60.19Q55.69 62.54 53.99 63.89Q52.29 65.25 49.26 65.25Q46.06 65.25 44.31 64.40L44.31 62.23Q45.46 62.72 46.82 63.01Q48.18 63.29 49.36 63.29Q51.38 63.29 52.40 62.51Q53.42 61.74 53.42 60.38Q53.42 59.48 53.05 58.91Q52.68 58.33 51.82 57.82Q50.95 57.32 49.19 56.68Q46.69 55.77 45.62 54.53Q44.55 53.29 44.55 51.33Q44.55 49.23 46.13 47.98Q47.71 46.74 50.30 46.74Q52.99 46.74 55.26 47.75L54.56 49.70Q52.24 48.74 50.25 48.74Q48.65 48.74 47.74 49.43Q46.84 50.11 46.84 51.36Q46.84 52.24 47.19 52.83Q47.54 53.41 48.32 53.89Q49.10 54.37 50.78 54.99Q52.78 55.73 53.77 56.42Q54.75 57.12 55.22 58.03Q55.69 58.93 55.69 60.19Z" fill="#c30" stroke="#c30" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="56.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="56.0" r="2.6" fill="#c30" />
+    <circle cx="50.0" cy="73.0" r="2.6" fill="#c30" />
+</g>""",
+"+n": """<g id="white-proknight" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".924" stroke-miterlimit="10" d="M73.5 19.4 50 11l-23.5 8.4-9 63V89h65v-6.6z" />
+    <path fill="#63451a" fill-opacity=".75" d="M18 82.4h64v6H18z" />
+    <path d="M56.34 64.00L53.99 64.00L45.42 50.71L45.33 50.71L45.39 51.60Q45.52 53.59 45.52 55.06L45.52 64.00L43.66 64.00L43.66 48.00L45.99 48.00L46.48 48.78L51.73 56.90L54.53 61.24L54.61 61.24Q54.59 60.98 54.53 59.37Q54.46 57.75 54.46 57.03L54.46 48.00L56.34 48.00L56.34 64.00Z" fill="#c30" stroke="#c30" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="56.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="56.0" r="2.6" fill="#c30" />
+    <circle cx="50.0" cy="73.0" r="2.6" fill="#c30" />
+</g>""",
+"+l": """<g id="white-prolance" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".924" stroke-miterlimit="10" d="M73.5 19.4 50 11l-23.5 8.4-9 63V89h65v-6.6z" />
+    <path fill="#63451a" fill-opacity=".75" d="M18 82.4h64v6H18z" />
+    <path d="M45.50 64.00L45.50 48.00L47.51 48.00L47.51 62.21L54.50 62.21L54.50 64.00L45.50 64.00Z" fill="#c30" stroke="#c30" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="56.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="56.0" r="2.6" fill="#c30" />
+    <circle cx="50.0" cy="73.0" r="2.6" fill="#c30" />
+</g>""",
+"+p": """<g id="white-tokin" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".944" stroke-miterlimit="10" d="M72.6 21.1 50 12.9l-22.6 8.2-8.7 61v5h62.6v-5z" />
+    <path opacity=".75" fill="#63451a" d="M19.2 82.1h61.6v4.5H19.2z" />
+    <path d="M55.12 52.71Q55.12 55.16 53.45 56.47Q51.77 57.77 48.73 57.77L46.89 57.77L46.89 64.00L44.88 64.00L44.88 48.00L49.11 48.00Q55.12 48.00 55.12 52.71ZM46.89 56.04L48.50 56.04Q50.89 56.04 51.96 55.27Q53.03 54.50 53.03 52.79Q53.03 51.25 52.04 50.48Q51.04 49.72 48.93 49.72L46.89 49.72L46.89 56.04Z" fill="#c30" stroke="#c30" stroke-width=".8" />
+    <circle cx="50.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="39.0" r="2.6" fill="#c30" />
+    <circle cx="67.0" cy="56.0" r="2.6" fill="#c30" />
+    <circle cx="33.0" cy="56.0" r="2.6" fill="#c30" />
+    <circle cx="50.0" cy="73.0" r="2.6" fill="#c30" />
+</g>""",
+}
@@ -0,0 +1,180 @@
+# Ryoko piece shapes with Latin letters, outlined from Noto Sans.
+
+PIECE_BACKGROUND = """<linearGradient id="a">
+        <stop offset="0" stop-color="#f9da9c" />
+        <stop offset=".043" stop-color="#fbe0a1" />
+        <stop offset=".067" stop-color="#fce6a6" />
+        <stop offset=".145" stop-color="#f4d593" />
+        <stop offset=".193" stop-color="#f5d797" />
+        <stop offset=".243" stop-color="#f7dda4" />
+        <stop offset=".263" stop-color="#f9e1ab" />
+        <stop offset=".329" stop-color="#f7dfa8" />
+        <stop offset=".383" stop-color="#f2d89e" />
+        <stop offset=".398" stop-color="#efd599" />
+        <stop offset=".461" stop-color="#f2d795" />
+        <stop offset=".462" stop-color="#f2d795" />
+        <stop offset=".527" stop-color="#f2d795" />
+        <stop offset=".538" stop-color="#f2d795" />
+        <stop offset=".538" stop-color="#f2d795" />
+        <stop offset=".615" stop-color="#efda9c" />
+        <stop offset=".735" stop-color="#f6e5ad" />
+        <stop offset=".773" stop-color="#f9e9b4" />
+        <stop offset=".822" stop-color="#f7e1a6" />
+        <stop offset=".887" stop-color="#f5db9b" />
+        <stop offset=".956" stop-color="#f4d997" />
+        <stop offset=".967" stop-color="#f4da9c" />
+        <stop offset="1" stop-color="#f4db9e"/>
+    </linearGradient>"""
+
+PIECES = {
+"K": """<g id="black-king">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M62.43 71.00L57.38 71.00L45.31 54.77L41.85 57.81L41.85 71.00L37.57 71.00L37.57 37.00L41.85 37.00L41.85 53.77L44.78 50.53L56.90 37.00L61.90 37.00L48.43 51.81L62.43 71.00Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width="1.2" />
+</g>""",
+"GY": """<g id="black-tama">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M62.43 71.00L57.38 71.00L45.31 54.77L41.85 57.81L41.85 71.00L37.57 71.00L37.57 37.00L41.85 37.00L41.85 53.77L44.78 50.53L56.90 37.00L61.90 37.00L48.43 51.81L62.43 71.00Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width="1.2" />
+</g>""",
+"R": """<g id="black-rook">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M42.14 53.33L47.23 53.33Q51.37 53.33 53.28 51.70Q55.19 50.07 55.19 46.86Q55.19 43.65 53.26 42.17Q51.33 40.70 47.00 40.70L42.14 40.70L42.14 53.33ZM42.14 56.95L42.14 71.00L37.86 71.00L37.86 37.00L47.23 37.00Q53.56 37.00 56.59 39.41Q59.63 41.81 59.63 46.65Q59.63 53.42 52.81 55.77L62.14 71.00L57.14 71.00L48.91 56.95L42.14 56.95Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width="1.2" />
+</g>""",
+"B": """<g id="black-bishop">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M38.12 37.00L47.81 37.00Q54.65 37.00 57.69 39.03Q60.72 41.07 60.72 45.42Q60.72 48.44 59.07 50.41Q57.42 52.37 54.26 52.95L54.26 53.19Q58.19 53.86 60.03 55.91Q61.88 57.95 61.88 61.33Q61.88 65.88 58.77 68.44Q55.65 71.00 50.07 71.00L38.12 71.00L38.12 37.00ZM42.40 51.47L48.67 51.47Q52.67 51.47 54.49 50.19Q56.30 48.91 56.30 45.91Q56.30 43.12 54.31 41.88Q52.33 40.65 48.02 40.65L42.40 40.65L42.40 51.47ZM42.40 55.05L42.40 67.37L49.26 67.37Q53.35 67.37 55.33 65.79Q57.30 64.21 57.30 61.00Q57.30 57.95 55.21 56.50Q53.12 55.05 48.93 55.05L42.40 55.05Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width="1.2" />
+</g>""",
+"G": """<g id="black-gold">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".92" stroke-miterlimit="10" d="M74.4 17.5 50 8.7l-24.4 8.8L16.2 84v7.3h67.6V84z" />
+    <path fill="#63451a" fill-opacity=".75" d="M16.7 84h66.7v6.8H16.7z" />
+    <path d="M52.23 53.10L63.29 53.10L63.29 68.80Q60.75 69.61 58.12 70.02Q55.49 70.44 52.04 70.44Q44.73 70.44 40.72 66.10Q36.71 61.77 36.71 54.00Q36.71 48.99 38.71 45.24Q40.70 41.50 44.45 39.52Q48.21 37.54 53.31 37.54Q58.43 37.54 62.85 39.42L61.32 42.92Q57.09 41.09 53.06 41.09Q47.35 41.09 44.13 44.54Q40.92 48.00 40.92 54.00Q40.92 60.35 44.00 63.65Q47.09 66.96 52.98 66.96Q56.19 66.96 59.26 66.19L59.26 56.69L52.23 56.69L52.23 53.10Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width="1.2" />
+</g>""",
+"S": """<g id="black-silver">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".922" stroke-miterlimit="10" d="M74.5 17.5 50 8.6l-24.5 8.9-9.4 66.6v7.3h67.8v-7.3z" />
+    <path fill="#63451a" fill-opacity=".75" d="M16.6 84.1h66.8v6.8H16.6z" />
+    <path d="M60.11 61.44Q60.11 65.62 57.09 68.03Q54.07 70.44 48.69 70.44Q43.00 70.44 39.89 68.93L39.89 65.08Q41.92 65.95 44.34 66.45Q46.76 66.96 48.86 66.96Q52.45 66.96 54.27 65.58Q56.08 64.20 56.08 61.79Q56.08 60.19 55.43 59.17Q54.77 58.14 53.23 57.24Q51.69 56.34 48.56 55.20Q44.11 53.58 42.21 51.38Q40.30 49.18 40.30 45.70Q40.30 41.96 43.12 39.75Q45.93 37.54 50.53 37.54Q55.32 37.54 59.35 39.34L58.10 42.79Q53.98 41.09 50.44 41.09Q47.59 41.09 45.98 42.31Q44.37 43.54 44.37 45.75Q44.37 47.32 45.00 48.36Q45.62 49.40 47.01 50.26Q48.40 51.11 51.38 52.21Q54.95 53.52 56.70 54.76Q58.45 55.99 59.28 57.60Q60.11 59.21 60.11 61.44Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width="1.2" />
+</g>""",
+"N": """<g id="black-knight">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".924" stroke-miterlimit="10" d="M73.5 19.4 50 11l-23.5 8.4-9 63V89h65v-6.6z" />
+    <path fill="#63451a" fill-opacity=".75" d="M18 82.4h64v6H18z" />
+    <path d="M61.89 69.00L57.48 69.00L41.41 44.09L41.25 44.09L41.35 45.75Q41.60 49.49 41.60 52.24L41.60 69.00L38.11 69.00L38.11 39.00L42.48 39.00L43.40 40.46L53.25 55.68L58.48 63.83L58.65 63.83Q58.61 63.34 58.48 60.31Q58.36 57.28 58.36 55.93L58.36 39.00L61.89 39.00L61.89 69.00Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width="1.2" />
+</g>""",
+"L": """<g id="black-lance">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".924" stroke-miterlimit="10" d="M73.5 19.4 50 11l-23.5 8.4-9 63V89h65v-6.6z" />
+    <path fill="#63451a" fill-opacity=".75" d="M18 82.4h64v6H18z" />
+    <path d="M41.56 69.00L41.56 39.00L45.33 39.00L45.33 65.63L58.44 65.63L58.44 69.00L41.56 69.00Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width="1.2" />
+</g>""",
+"P": """<g id="black-pawn">
+    <path d="M72.6 21.1 50 12.9l-22.6 8.2-8.7 61v5h62.6v-5z" style="fill:url(#a);stroke:#63451a;stroke-width:.94400001;stroke-miterlimit:10" />
+    <path d="M19.2 82.1h61.6v4.5H19.2z" style="opacity:.75;fill:#63451a" />
+    <path d="M59.60 47.82Q59.60 52.42 56.46 54.87Q53.32 57.32 47.62 57.32L44.17 57.32L44.17 69.00L40.40 69.00L40.40 39.00L48.34 39.00Q59.60 39.00 59.60 47.82ZM44.17 54.08L47.19 54.08Q51.66 54.08 53.67 52.64Q55.68 51.19 55.68 47.99Q55.68 45.09 53.82 43.66Q51.95 42.22 47.99 42.22L44.17 42.22L44.17 54.08Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width="1.2" />
+</g>""",
+"+R": """<g id="black-dragon">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M42.14 53.33L47.23 53.33Q51.37 53.33 53.28 51.70Q55.19 50.07 55.19 46.86Q55.19 43.65 53.26 42.17Q51.33 40.70 47.00 40.70L42.14 40.70L42.14 53.33ZM42.14 56.95L42.14 71.00L37.86 71.00L37.86 37.00L47.23 37.00Q53.56 37.00 56.59 39.41Q59.63 41.81 59.63 46.65Q59.63 53.42 52.81 55.77L62.14 71.00L57.14 71.00L48.91 56.95L42.14 56.95Z" fill="#c30" stroke="#c30" stroke-width="1.2" />
+</g>""",
+"+B": """<g id="black-horse">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M38.12 37.00L47.81 37.00Q54.65 37.00 57.69 39.03Q60.72 41.07 60.72 45.42Q60.72 48.44 59.07 50.41Q57.42 52.37 54.26 52.95L54.26 53.19Q58.19 53.86 60.03 55.91Q61.88 57.95 61.88 61.33Q61.88 65.88 58.77 68.44Q55.65 71.00 50.07 71.00L38.12 71.00L38.12 37.00ZM42.40 51.47L48.67 51.47Q52.67 51.47 54.49 50.19Q56.30 48.91 56.30 45.91Q56.30 43.12 54.31 41.88Q52.33 40.65 48.02 40.65L42.40 40.65L42.40 51.47ZM42.40 55.05L42.40 67.37L49.26 67.37Q53.35 67.37 55.33 65.79Q57.30 64.21 57.30 61.00Q57.30 57.95 55.21 56.50Q53.12 55.05 48.93 55.05L42.40 55.05Z" fill="#c30" stroke="#c30" stroke-width="1.2" />
+</g>""",
+"+S": """<g id="black-prosilver">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".92" stroke-miterlimit="10" d="M74.4 17.5 50 8.7l-24.4 8.8L16.2 84v7.3h67.6V84z" />
+    <path fill="#63451a" fill-opacity=".75" d="M16.7 84h66.7v6.8H16.7z" />
+    <path d="M60.11 61.44Q60.11 65.62 57.09 68.03Q54.07 70.44 48.69 70.44Q43.00 70.44 39.89 68.93L39.89 65.08Q41.92 65.95 44.34 66.45Q46.76 66.96 48.86 66.96Q52.45 66.96 54.27 65.58Q56.08 64.20 56.08 61.79Q56.08 60.19 55.43 59.17Q54.77 58.14 53.23 57.24Q51.69 56.34 48.56 55.20Q44.11 53.58 42.21 51.38Q40.30 49.18 40.30 45.70Q40.30 41.96 43.12 39.75Q45.93 37.54 50.53 37.54Q55.32 37.54 59.35 39.34L58.10 42.79Q53.98 41.09 50.44 41.09Q47.59 41.09 45.98 42.31Q44.37 43.54 44.37 45.75Q44.37 47.32 45.00 48.36Q45.62 49.40 47.01 50.26Q48.40 51.11 51.38 52.21Q54.95 53.52 56.70 54.76Q58.45 55.99 59.28 57.60Q60.11 59.21 60.11 61.44Z" fill="#c30" stroke="#c30" stroke-width="1.2" />
+</g>""",
+"+N": """<g id="black-proknight">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".924" stroke-miterlimit="10" d="M73.5 19.4 50 11l-23.5 8.4-9 63V89h65v-6.6z" />
+    <path fill="#63451a" fill-opacity=".75" d="M18 82.4h64v6H18z" />
+    <path d="M61.89 69.00L57.48 69.00L41.41 44.09L41.25 44.09L41.35 45.75Q41.60 49.49 41.60 52.24L41.60 69.00L38.11 69.00L38.11 39.00L42.48 39.00L43.40 40.46L53.25 55.68L58.48 63.83L58.65 63.83Q58.61 63.34 58.48 60.31Q58.36 57.28 58.36 55.93L58.36 39.00L61.89 39.00L61.89 69.00Z" fill="#c30" stroke="#c30" stroke-width="1.2" />
+</g>""",
+"+L": """<g id="black-prolance">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".924" stroke-miterlimit="10" d="M73.5 19.4 50 11l-23.5 8.4-9 63V89h65v-6.6z" />
+    <path fill="#63451a" fill-opacity=".75" d="M18 82.4h64v6H18z" />
+    <path d="M41.56 69.00L41.56 39.00L45.33 39.00L45.33 65.63L58.44 65.63L58.44 69.00L41.56 69.00Z" fill="#c30" stroke="#c30" stroke-width="1.2" />
+</g>""",
+"+P": """<g id="black-tokin">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".944" stroke-miterlimit="10" d="M72.6 21.1 50 12.9l-22.6 8.2-8.7 61v5h62.6v-5z" />
+    <path opacity=".75" fill="#63451a" d="M19.2 82.1h61.6v4.5H19.2z" />
+    <path d="M59.60 47.82Q59.60 52.42 56.46 54.87Q53.32 57.32 47.62 57.32L44.17 57.32L44.17 69.00L40.40 69.00L40.40 39.00L48.34 39.00Q59.60 39.00 59.60 47.82ZM44.17 54.08L47.19 54.08Q51.66 54.08 53.67 52.64Q55.68 51.19 55.68 47.99Q55.68 45.09 53.82 43.66Q51.95 42.22 47.99 42.22L44.17 42.22L44.17 54.08Z" fill="#c30" stroke="#c30" stroke-width="1.2" />
+</g>""",
+"k": """<g id="white-king" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M62.43 71.00L57.38 71.00L45.31 54.77L41.85 57.81L41.85 71.00L37.57 71.00L37.57 37.00L41.85 37.00L41.85 53.77L44.78 50.53L56.90 37.00L61.90 37.00L48.43 51.81L62.43 71.00Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width="1.2" />
+</g>""",
+"gy": """<g id="white-tama" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M62.43 71.00L57.38 71.00L45.31 54.77L41.85 57.81L41.85 71.00L37.57 71.00L37.57 37.00L41.85 37.00L41.85 53.77L44.78 50.53L56.90 37.00L61.90 37.00L48.43 51.81L62.43 71.00Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width="1.2" />
+</g>""",
+"r": """<g id="white-rook" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M42.14 53.33L47.23 53.33Q51.37 53.33 53.28 51.70Q55.19 50.07 55.19 46.86Q55.19 43.65 53.26 42.17Q51.33 40.70 47.00 40.70L42.14 40.70L42.14 53.33ZM42.14 56.95L42.14 71.00L37.86 71.00L37.86 37.00L47.23 37.00Q53.56 37.00 56.59 39.41Q59.63 41.81 59.63 46.65Q59.63 53.42 52.81 55.77L62.14 71.00L57.14 71.00L48.91 56.95L42.14 56.95Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width="1.2" />
+</g>""",
+"b": """<g id="white-bishop" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M38.12 37.00L47.81 37.00Q54.65 37.00 57.69 39.03Q60.72 41.07 60.72 45.42Q60.72 48.44 59.07 50.41Q57.42 52.37 54.26 52.95L54.26 53.19Q58.19 53.86 60.03 55.91Q61.88 57.95 61.88 61.33Q61.88 65.88 58.77 68.44Q55.65 71.00 50.07 71.00L38.12 71.00L38.12 37.00ZM42.40 51.47L48.67 51.47Q52.67 51.47 54.49 50.19Q56.30 48.91 56.30 45.91Q56.30 43.12 54.31 41.88Q52.33 40.65 48.02 40.65L42.40 40.65L42.40 51.47ZM42.40 55.05L42.40 67.37L49.26 67.37Q53.35 67.37 55.33 65.79Q57.30 64.21 57.30 61.00Q57.30 57.95 55.21 56.50Q53.12 55.05 48.93 55.05L42.40 55.05Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width="1.2" />
+</g>""",
+"g": """<g id="white-gold" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".92" stroke-miterlimit="10" d="M74.4 17.5 50 8.7l-24.4 8.8L16.2 84v7.3h67.6V84z" />
+    <path fill="#63451a" fill-opacity=".75" d="M16.7 84h66.7v6.8H16.7z" />
+    <path d="M52.23 53.10L63.29 53.10L63.29 68.80Q60.75 69.61 58.12 70.02Q55.49 70.44 52.04 70.44Q44.73 70.44 40.72 66.10Q36.71 61.77 36.71 54.00Q36.71 48.99 38.71 45.24Q40.70 41.50 44.45 39.52Q48.21 37.54 53.31 37.54Q58.43 37.54 62.85 39.42L61.32 42.92Q57.09 41.09 53.06 41.09Q47.35 41.09 44.13 44.54Q40.92 48.00 40.92 54.00Q40.92 60.35 44.00 63.65Q47.09 66.96 52.98 66.96Q56.19 66.96 59.26 66.19L59.26 56.69L52.23 56.69L52.23 53.10Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width="1.2" />
+</g>""",
+"s": """<g id="white-silver" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".922" stroke-miterlimit="10" d="M74.5 17.5 50 8.6l-24.5 8.9-9.4 66.6v7.3h67.8v-7.3z" />
+    <path fill="#63451a" fill-opacity=".75" d="M16.6 84.1h66.8v6.8H16.6z" />
+    <path d="M60.11 61.44Q60.11 65.62 57.09 68.03Q54.07 70.44 48.69 70.44Q43.00 70.44 39.89 68.93L39.89 65.08Q41.92 65.95 44.34 66.45Q46.76 66.96 48.86 66.96Q52.45 66.96 54.27 65.58Q56.08 64.20 56.08 61.79Q56.08 60.19 55.43 59.17Q54.77 58.14 53.23 57.24Q51.69 56.34 48.56 55.20Q44.11 53.58 42.21 51.38Q40.30 49.18 40.30 45.70Q40.30 41.96 43.12 39.75Q45.93 37.54 50.53 37.54Q55.32 37.54 59.35 39.34L58.10 42.79Q53.98 41.09 50.44 41.09Q47.59 41.09 45.98 42.31Q44.37 43.54 44.37 45.75Q44.37 47.32 45.00 48.36Q45.62 49.40 47.01 50.26Q48.40 51.11 51.38 52.21Q54.95 53.52 56.70 54.76Q58.45 55.99 59.28 57.60Q60.11 59.21 60.11 61.44Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width="1.2" />
+</g>""",
+"n": """<g id="white-knight" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".924" stroke-miterlimit="10" d="M73.5 19.4 50 11l-23.5 8.4-9 63V89h65v-6.6z" />
+    <path fill="#63451a" fill-opacity=".75" d="M18 82.4h64v6H18z" />
+    <path d="M61.89 69.00L57.48 69.00L41.41 44.09L41.25 44.09L41.35 45.75Q41.60 49.49 41.60 52.24L41.60 69.00L38.11 69.00L38.11 39.00L42.48 39.00L43.40 40.46L53.25 55.68L58.48 63.83L58.65 63.83Q58.61 63.34 58.48 60.31Q58.36 57.28 58.36 55.93L58.36 39.00L61.89 39.00L61.89 69.00Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width="1.2" />
+</g>""",
+"l": """<g id="white-lance" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".924" stroke-miterlimit="10" d="M73.5 19.4 50 11l-23.5 8.4-9 63V89h65v-6.6z" />
+    <path fill="#63451a" fill-opacity=".75" d="M18 82.4h64v6H18z" />
+    <path d="M41.56 69.00L41.56 39.00L45.33 39.00L45.33 65.63L58.44 65.63L58.44 69.00L41.56 69.00Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width="1.2" />
+</g>""",
+"p": """<g id="white-pawn" transform="rotate(180 50 50)">
+    <path d="M72.6 21.1 50 12.9l-22.6 8.2-8.7 61v5h62.6v-5z" style="fill:url(#a);stroke:#63451a;stroke-width:.94400001;stroke-miterlimit:10" />
+    <path d="M19.2 82.1h61.6v4.5H19.2z" style="opacity:.75;fill:#63451a" />
+    <path d="M59.60 47.82Q59.60 52.42 56.46 54.87Q53.32 57.32 47.62 57.32L44.17 57.32L44.17 69.00L40.40 69.00L40.40 39.00L48.34 39.00Q59.60 39.00 59.60 47.82ZM44.17 54.08L47.19 54.08Q51.66 54.08 53.67 52.64Q55.68 51.19 55.68 47.99Q55.68 45.09 53.82 43.66Q51.95 42.22 47.99 42.22L44.17 42.22L44.17 54.08Z" fill="#1a1a1a" stroke="#1a1a1a" stroke-width="1.2" />
+</g>""",
+"+r": """<g id="white-dragon" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M42.14 53.33L47.23 53.33Q51.37 53.33 53.28 51.70Q55.19 50.07 55.19 46.86Q55.19 43.65 53.26 42.17Q51.33 40.70 47.00 40.70L42.14 40.70L42.14 53.33ZM42.14 56.95L42.14 71.00L37.86 71.00L37.86 37.00L47.23 37.00Q53.56 37.00 56.59 39.41Q59.63 41.81 59.63 46.65Q59.63 53.42 52.81 55.77L62.14 71.00L57.14 71.00L48.91 56.95L42.14 56.95Z" fill="#c30" stroke="#c30" stroke-width="1.2" />
+</g>""",
+"+b": """<g id="white-horse" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".954" stroke-miterlimit="10" d="M77.6 15.4 50 5.8l-27.6 9.6-10.6 72.1v6.7h76.4v-6.7z" />
+    <path fill="#63451a" fill-opacity=".75" d="M12.3 87.5h75.4v6.2H12.3z" />
+    <path d="M38.12 37.00L47.81 37.00Q54.65 37.00 57.69 39.03Q60.72 41.07 60.72 45.42Q60.72 48.44 59.07 50.41Q57.42 52.37 54.26 52.95L54.26 53.19Q58.19 53.86 60.03 55.91Q61.88 57.95 61.88 61.33Q61.88 65.88 58.77 68.44Q55.65 71.00 50.07 71.00L38.12 71.00L38.12 37.00ZM42.40 51.47L48.67 51.47Q52.67 51.47 54.49 50.19Q56.30 48.91 56.30 45.91Q56.30 43.12 54.31 41.88Q52.33 40.65 48.02 40.65L42.40 40.65L42.40 51.47ZM42.40 55.05L42.40 67.37L49.26 67.37Q53.35 67.37 55.33 65.79Q57.30 64.21 57.30 61.00Q57.30 57.95 55.21 56.50Q53.12 55.05 48.93 55.05L42.40 55.05Z" fill="#c30" stroke="#c30" stroke-width="1.2" />
+</g>""",
+"+s": """<g id="white-prosilver" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".92" stroke-miterlimit="10" d="M74.4 17.5 50 8.7l-24.4 8.8L16.2 84v7.3h67.6V84z" />
+    <path fill="#63451a" fill-opacity=".75" d="M16.7 84h66.7v6.8H16.7z" />
+    <path d="M60.11 61.44Q60.11 65.62 57.09 68.03Q54.07 70.44 48.69 70.44Q43.00 70.44 39.89 68.93L39.89 65.08Q41.92 65.95 44.34 66.45Q46.76 66.96 48.86 66.96Q52.45 66.96 54.27 65.58Q56.08 64.20 56.08 61.79Q56.08 60.19 55.43 59.17Q54.77 58.14 53.23 57.24Q51.69 56.34 48.56 55.20Q44.11 53.58 42.21 51.38Q40.30 49.18 40.30 45.70Q40.30 41.96 43.12 39.75Q45.93 37.54 50.53 37.54Q55.32 37.54 59.35 39.34L58.10 42.79Q53.98 41.09 50.44 41.09Q47.59 41.09 45.98 42.31Q44.37 43.54 44.37 45.75Q44.37 47.32 45.00 48.36Q45.62 49.40 47.01 50.26Q48.40 51.11 51.38 52.21Q54.95 53.52 56.70 54.76Q58.45 55.99 59.28 57.60Q60.11 59.21 60.11 61.44Z" fill="#c30" stroke="#c30" stroke-width="1.2" />
+</g>""",
+"+n": """<g id="white-proknight" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".924" stroke-miterlimit="10" d="M73.5 19.4 50 11l-23.5 8.4-9 63V89h65v-6.6z" />
+    <path fill="#63451a" fill-opacity=".75" d="M18 82.4h64v6H18z" />
+    <path d="M61.89 69.00L57.48 69.00L41.41 44.09L41.25 44.09L41.35 45.75Q41.60 49.49 41.60 52.24L41.60 69.00L38.11 69.00L38.11 39.00L42.48 39.00L43.40 40.46L53.25 55.68L58.48 63.83L58.65 63.83Q58.61 63.34 58.48 60.31Q58.36 57.28 58.36 55.93L58.36 39.00L61.89 39.00L61.89 69.00Z" fill="#c30" stroke="#c30" stroke-width="1.2" />
+</g>""",
+"+l": """<g id="white-prolance" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".924" stroke-miterlimit="10" d="M73.5 19.4 50 11l-23.5 8.4-9 63V89h65v-6.6z" />
+    <path fill="#63451a" fill-opacity=".75" d="M18 82.4h64v6H18z" />
+    <path d="M41.56 69.00L41.56 39.00L45.33 39.00L45.33 65.63L58.44 65.63L58.44 69.00L41.56 69.00Z" fill="#c30" stroke="#c30" stroke-width="1.2" />
+</g>""",
+"+p": """<g id="white-tokin" transform="rotate(180 50 50)">
+    <path fill="url(#a)" stroke="#63451a" stroke-width=".944" stroke-miterlimit="10" d="M72.6 21.1 50 12.9l-22.6 8.2-8.7 61v5h62.6v-5z" />
+    <path opacity=".75" fill="#63451a" d="M19.2 82.1h61.6v4.5H19.2z" />
+    <path d="M59.60 47.82Q59.60 52.42 56.46 54.87Q53.32 57.32 47.62 57.32L44.17 57.32L44.17 69.00L40.40 69.00L40.40 39.00L48.34 39.00Q59.60 39.00 59.60 47.82ZM44.17 54.08L47.19 54.08Q51.66 54.08 53.67 52.64Q55.68 51.19 55.68 47.99Q55.68 45.09 53.82 43.66Q51.95 42.22 47.99 42.22L44.17 42.22L44.17 54.08Z" fill="#c30" stroke="#c30" stroke-width="1.2" />
+</g>""",
+}
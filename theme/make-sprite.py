@@ -1,11 +1,25 @@
+import importlib
+import sys
 import xml.etree.ElementTree as ET
 import chess.svg
 
-import dist.pieces as TP
+# Square and highlighted square colors.
+BOARDS = {
+    "orange": ["#f4bf57", "#d0c333"],
+    "wood": ["#d8a86a", "#c7b342"],
+    "blue": ["#a3bdd6", "#c2cb5e"],
+    "gray": ["#c2c2c2", "#d0c84a"],
+}
+
+PIECE_SETS = {
+    "ryoko_1kanji": "dist.pieces",
+    "western": "dist.western",
+    "international": "dist.international",
+}
 
 COLORS = [
-    "#f4bf57", # square
-    "#d0c333", # highlighted square
+    None, # square, from the board
+    None, # highlighted square, from the board
     "#262421", # dark background
     "#bababa", # text color
     "#bf811d", # title color
@@ -40,7 +54,10 @@ SQUARE_WIDTH = 11 * SCALE
 SQUARE_HEIGHT = 12 * SCALE
 
 
-def make_sprite(f):
+def make_sprite(f, board="orange", piece_set="ryoko_1kanji"):
+    colors = BOARDS[board] + COLORS[2:]
+    TP = importlib.import_module(PIECE_SETS[piece_set])
+
     svg = ET.Element("svg", {
         "xmlns": "http://www.w3.org/2000/svg",
         "version": "1.1",
@@ -55,7 +72,7 @@ def make_sprite(f):
 
     defs.append(ET.fromstring(chess.svg.CHECK_GRADIENT))
 
-    for x, color in enumerate(COLORS[2:]):
+    for x, color in enumerate(colors[2:]):
         ET.SubElement(svg, "rect", {
             "x": str(SQUARE_WIDTH * x),
             "y": "0",
@@ -72,7 +89,7 @@ def make_sprite(f):
         "width": str(SQUARE_WIDTH * 4),
         "height": str(7 * SQUARE_HEIGHT),
         "stroke": "none",
-        "fill": COLORS[7],
+        "fill": colors[7],
     })
 
     for x in range(4):
@@ -98,7 +115,7 @@ def make_sprite(f):
                     "stroke": "#fff",
                     "stroke-width": "2",
                     "rx": "8",
-                    "fill": COLORS[2],
+                    "fill": colors[2],
                 })
 
     ET.SubElement(svg, "circle", {
@@ -119,7 +136,7 @@ def make_sprite(f):
                     "height": str(SQUARE_HEIGHT),
                     "stroke": "#000",
                     "stroke-width": "2",
-                    "fill": COLORS[x % 2],
+                    "fill": colors[x % 2],
                 })
 
             if y > 4 and (x == 6 or x == 7):
@@ -160,4 +177,14 @@ def make_sprite(f):
     f.write(ET.tostring(svg))
 
 if __name__ == "__main__":
-    make_sprite(open("sprite.svg", "wb"))
+    # Usage: make-sprite.py [board] [piece set]
+    args = sys.argv[1:]
+    if len(args) > 2 or any(arg not in known for arg, known in zip(args, [BOARDS, PIECE_SETS])):
+        print(f"usage: make-sprite.py [{'|'.join(BOARDS)}] [{'|'.join(PIECE_SETS)}]", file=sys.stderr)
+        sys.exit(1)
+    if args:
+        board = args[0]
+        piece_set = args[1] if len(args) > 1 else "ryoko_1kanji"
+        make_sprite(open(f"sprite-{board}-{piece_set}.svg", "wb"), board, piece_set)
+    else:
+        make_sprite(open("sprite.svg", "wb"))